@@ -0,0 +1,751 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdError, StdResult,
+};
+
+use cw2::set_contract_version;
+use cw721::{
+    AllNftInfoResponse, ApprovedForAllResponse, ContractInfoResponse, Cw721Execute, Cw721Query,
+    Cw721ReceiveMsg, Expiration, NftInfoResponse, NumTokensResponse, OwnerOfResponse,
+    TokensResponse,
+};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MinterResponse, QueryMsg};
+use crate::state::{tokens, Approval, TokenInfo, CONTRACT_INFO, MINTER, OPERATORS, TOKEN_COUNT};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:club-nft";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const CLUB_DESCRIPTION: &str = "Ownership of a Crypto 11 club";
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+/// The club tokens. Implements the cw721 traits on top of the storage in state.rs
+pub struct ClubNftContract;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONTRACT_INFO.save(
+        deps.storage,
+        &ContractInfoResponse {
+            name: msg.name,
+            symbol: msg.symbol,
+        },
+    )?;
+    MINTER.save(deps.storage, &deps.api.addr_validate(&msg.minter)?)?;
+    TOKEN_COUNT.save(deps.storage, &0u64)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let contract = ClubNftContract;
+    match msg {
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => contract.transfer_nft(deps, env, info, recipient, token_id),
+        ExecuteMsg::SendNft {
+            contract: receiver,
+            token_id,
+            msg,
+        } => contract.send_nft(deps, env, info, receiver, token_id, msg),
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => contract.approve(deps, env, info, spender, token_id, expires),
+        ExecuteMsg::Revoke { spender, token_id } => {
+            contract.revoke(deps, env, info, spender, token_id)
+        }
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            contract.approve_all(deps, env, info, operator, expires)
+        }
+        ExecuteMsg::RevokeAll { operator } => contract.revoke_all(deps, env, info, operator),
+        ExecuteMsg::Mint { token_id, owner } => mint(deps, info, token_id, owner),
+    }
+}
+
+fn mint(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    owner: String,
+) -> Result<Response, ContractError> {
+    let minter = MINTER.load(deps.storage)?;
+    if info.sender != minter {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token = TokenInfo {
+        owner: deps.api.addr_validate(&owner)?,
+        approvals: vec![],
+        name: token_id.clone(),
+        description: String::from(CLUB_DESCRIPTION),
+        image: None,
+    };
+    tokens().update(deps.storage, token_id.clone(), |old| match old {
+        Some(_) => Err(ContractError::Claimed {}),
+        None => Ok(token),
+    })?;
+    TOKEN_COUNT.update(deps.storage, |count| -> StdResult<u64> { Ok(count + 1) })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("minter", info.sender)
+        .add_attribute("owner", owner)
+        .add_attribute("token_id", token_id))
+}
+
+impl Cw721Execute<Empty, Empty> for ClubNftContract {
+    type Err = ContractError;
+
+    fn transfer_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        token_id: String,
+    ) -> Result<Response, ContractError> {
+        let hook = transfer_club(deps, &env, &info, &recipient, &token_id)?;
+
+        Ok(Response::new()
+            .add_message(hook)
+            .add_attribute("action", "transfer_nft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", recipient)
+            .add_attribute("token_id", token_id))
+    }
+
+    fn send_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    ) -> Result<Response, ContractError> {
+        let hook = transfer_club(deps, &env, &info, &contract, &token_id)?;
+
+        let send = Cw721ReceiveMsg {
+            sender: info.sender.to_string(),
+            token_id: token_id.clone(),
+            msg,
+        };
+        Ok(Response::new()
+            .add_message(send.into_cosmos_msg(contract.clone())?)
+            .add_message(hook)
+            .add_attribute("action", "send_nft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", contract)
+            .add_attribute("token_id", token_id))
+    }
+
+    fn approve(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        update_approvals(deps, &env, &info, &spender, &token_id, true, expires)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "approve")
+            .add_attribute("sender", info.sender)
+            .add_attribute("spender", spender)
+            .add_attribute("token_id", token_id))
+    }
+
+    fn revoke(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: String,
+    ) -> Result<Response, ContractError> {
+        update_approvals(deps, &env, &info, &spender, &token_id, false, None)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke")
+            .add_attribute("sender", info.sender)
+            .add_attribute("spender", spender)
+            .add_attribute("token_id", token_id))
+    }
+
+    fn approve_all(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        operator: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        // reject expired data as invalid
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        OPERATORS.save(deps.storage, (info.sender.clone(), operator_addr), &expires)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "approve_all")
+            .add_attribute("sender", info.sender)
+            .add_attribute("operator", operator))
+    }
+
+    fn revoke_all(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        operator: String,
+    ) -> Result<Response, ContractError> {
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        OPERATORS.remove(deps.storage, (info.sender.clone(), operator_addr));
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_all")
+            .add_attribute("sender", info.sender)
+            .add_attribute("operator", operator))
+    }
+}
+
+// Moves the club token to the recipient and returns the message that lets the
+// club staking contract pick up the new owner
+fn transfer_club(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: &str,
+    token_id: &str,
+) -> Result<CosmosMsg, ContractError> {
+    let mut token = tokens().load(deps.storage, token_id.to_string())?;
+    check_can_send(deps.as_ref(), env, info, &token)?;
+
+    token.owner = deps.api.addr_validate(recipient)?;
+    token.approvals = vec![];
+    tokens().save(deps.storage, token_id.to_string(), &token)?;
+
+    let minter = MINTER.load(deps.storage)?;
+    let hook = Cw721ReceiveMsg {
+        sender: info.sender.to_string(),
+        token_id: token_id.to_string(),
+        msg: Binary::default(),
+    };
+    Ok(hook.into_cosmos_msg(minter)?)
+}
+
+fn update_approvals(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    spender: &str,
+    token_id: &str,
+    add: bool,
+    expires: Option<Expiration>,
+) -> Result<TokenInfo, ContractError> {
+    let mut token = tokens().load(deps.storage, token_id.to_string())?;
+    // ensure we have permissions
+    check_can_approve(deps.as_ref(), env, info, &token)?;
+
+    // update the approval list (remove any for the same spender before adding)
+    let spender_addr = deps.api.addr_validate(spender)?;
+    token.approvals.retain(|apr| apr.spender != spender_addr);
+
+    if add {
+        // reject expired data as invalid
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+        token.approvals.push(Approval {
+            spender: spender_addr,
+            expires,
+        });
+    }
+
+    tokens().save(deps.storage, token_id.to_string(), &token)?;
+    Ok(token)
+}
+
+// the owner or one of its operators can change approvals of a token
+fn check_can_approve(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token: &TokenInfo,
+) -> Result<(), ContractError> {
+    if token.owner == info.sender {
+        return Ok(());
+    }
+    check_operator(deps, env, info, token)
+}
+
+// the owner, an approved spender or one of the owner's operators can move a token
+fn check_can_send(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token: &TokenInfo,
+) -> Result<(), ContractError> {
+    if token.owner == info.sender {
+        return Ok(());
+    }
+    if token
+        .approvals
+        .iter()
+        .any(|apr| apr.spender == info.sender && !apr.is_expired(&env.block))
+    {
+        return Ok(());
+    }
+    check_operator(deps, env, info, token)
+}
+
+fn check_operator(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token: &TokenInfo,
+) -> Result<(), ContractError> {
+    let op = OPERATORS.may_load(deps.storage, (token.owner.clone(), info.sender.clone()))?;
+    match op {
+        Some(ex) if !ex.is_expired(&env.block) => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let contract = ClubNftContract;
+    match msg {
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => to_binary(&contract.owner_of(
+            deps,
+            env,
+            token_id,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::ApprovedForAll {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => to_binary(&contract.all_approvals(
+            deps,
+            env,
+            owner,
+            include_expired.unwrap_or(false),
+            start_after,
+            limit,
+        )?),
+        QueryMsg::NumTokens {} => to_binary(&contract.num_tokens(deps)?),
+        QueryMsg::ContractInfo {} => to_binary(&contract.contract_info(deps)?),
+        QueryMsg::NftInfo { token_id } => to_binary(&contract.nft_info(deps, token_id)?),
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => to_binary(&contract.all_nft_info(
+            deps,
+            env,
+            token_id,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&contract.tokens(deps, owner, start_after, limit)?),
+        QueryMsg::AllTokens { start_after, limit } => {
+            to_binary(&contract.all_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::Minter {} => to_binary(&query_minter(deps)?),
+    }
+}
+
+fn query_minter(deps: Deps) -> StdResult<MinterResponse> {
+    let minter = MINTER.load(deps.storage)?;
+    Ok(MinterResponse {
+        minter: minter.to_string(),
+    })
+}
+
+fn humanize_approvals(
+    block: &BlockInfo,
+    token: &TokenInfo,
+    include_expired: bool,
+) -> Vec<cw721::Approval> {
+    token
+        .approvals
+        .iter()
+        .filter(|apr| include_expired || !apr.is_expired(block))
+        .map(|apr| cw721::Approval {
+            spender: apr.spender.to_string(),
+            expires: apr.expires,
+        })
+        .collect()
+}
+
+impl Cw721Query<Empty> for ClubNftContract {
+    fn contract_info(&self, deps: Deps) -> StdResult<ContractInfoResponse> {
+        CONTRACT_INFO.load(deps.storage)
+    }
+
+    fn num_tokens(&self, deps: Deps) -> StdResult<NumTokensResponse> {
+        let count = TOKEN_COUNT.may_load(deps.storage)?.unwrap_or_default();
+        Ok(NumTokensResponse { count })
+    }
+
+    fn nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<Empty>> {
+        let token = tokens().load(deps.storage, token_id)?;
+        Ok(NftInfoResponse {
+            name: token.name,
+            description: token.description,
+            image: token.image,
+            extension: Empty {},
+        })
+    }
+
+    fn owner_of(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<OwnerOfResponse> {
+        let token = tokens().load(deps.storage, token_id)?;
+        Ok(OwnerOfResponse {
+            owner: token.owner.to_string(),
+            approvals: humanize_approvals(&env.block, &token, include_expired),
+        })
+    }
+
+    fn all_approvals(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ApprovedForAllResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let operators: StdResult<Vec<_>> = OPERATORS
+            .prefix(owner_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|item| match item {
+                Ok((_, expires)) => include_expired || !expires.is_expired(&env.block),
+                Err(_) => true,
+            })
+            .take(limit)
+            .map(|item| {
+                let (k, expires) = item?;
+                Ok(cw721::Approval {
+                    spender: String::from_utf8(k)?,
+                    expires,
+                })
+            })
+            .collect();
+        Ok(ApprovedForAllResponse {
+            operators: operators?,
+        })
+    }
+
+    fn tokens(
+        &self,
+        deps: Deps,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let tokens: StdResult<Vec<String>> = tokens()
+            .idx
+            .owner
+            .prefix(owner_addr.to_string())
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|k| String::from_utf8(k).map_err(StdError::from))
+            .collect();
+        Ok(TokensResponse { tokens: tokens? })
+    }
+
+    fn all_tokens(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let tokens: StdResult<Vec<String>> = tokens()
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (k, _) = item?;
+                String::from_utf8(k).map_err(StdError::from)
+            })
+            .collect();
+        Ok(TokensResponse { tokens: tokens? })
+    }
+
+    fn all_nft_info(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<AllNftInfoResponse<Empty>> {
+        let token = tokens().load(deps.storage, token_id)?;
+        Ok(AllNftInfoResponse {
+            access: OwnerOfResponse {
+                owner: token.owner.to_string(),
+                approvals: humanize_approvals(&env.block, &token, include_expired),
+            },
+            info: NftInfoResponse {
+                name: token.name,
+                description: token.description,
+                image: token.image,
+                extension: Empty {},
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_binary, SubMsg, WasmMsg};
+
+    const MINTER_ADDR: &str = "club_staking11111";
+
+    fn setup(deps: DepsMut) {
+        let msg = InstantiateMsg {
+            name: "Crypto 11 Clubs".to_string(),
+            symbol: "CLUB".to_string(),
+            minter: MINTER_ADDR.to_string(),
+        };
+        instantiate(deps, mock_env(), mock_info("admin11111", &[]), msg).unwrap();
+    }
+
+    fn mint_club(deps: DepsMut, club_name: &str, owner: &str) {
+        execute(
+            deps,
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            ExecuteMsg::Mint {
+                token_id: club_name.to_string(),
+                owner: owner.to_string(),
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_only_minter_can_mint_once_per_club() {
+        let mut deps = mock_dependencies(&[]);
+        setup(deps.as_mut());
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("Owner001", &[]),
+            ExecuteMsg::Mint {
+                token_id: "CLUB001".to_string(),
+                owner: "Owner001".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        mint_club(deps.as_mut(), "CLUB001", "Owner001");
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            ExecuteMsg::Mint {
+                token_id: "CLUB001".to_string(),
+                owner: "Owner002".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Claimed {});
+
+        let count: NumTokensResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::NumTokens {}).unwrap())
+                .unwrap();
+        assert_eq!(count.count, 1);
+        let info: NftInfoResponse<Empty> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::NftInfo {
+                    token_id: "CLUB001".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(info.name, "CLUB001".to_string());
+    }
+
+    #[test]
+    fn test_transfer_notifies_club_staking() {
+        let mut deps = mock_dependencies(&[]);
+        setup(deps.as_mut());
+        mint_club(deps.as_mut(), "CLUB001", "Owner001");
+
+        // only the owner, an approved spender or an operator may move the token
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("Owner002", &[]),
+            ExecuteMsg::TransferNft {
+                recipient: "Owner002".to_string(),
+                token_id: "CLUB001".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("Owner001", &[]),
+            ExecuteMsg::ApproveAll {
+                operator: "marketplace11111".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("marketplace11111", &[]),
+            ExecuteMsg::TransferNft {
+                recipient: "Owner002".to_string(),
+                token_id: "CLUB001".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MINTER_ADDR.to_string(),
+                msg: Cw721ReceiveMsg {
+                    sender: "marketplace11111".to_string(),
+                    token_id: "CLUB001".to_string(),
+                    msg: Binary::default(),
+                }
+                .into_binary()
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+
+        let owner: OwnerOfResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::OwnerOf {
+                    token_id: "CLUB001".to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(owner.owner, "Owner002".to_string());
+
+        let owned: TokensResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Tokens {
+                    owner: "Owner001".to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(owned.tokens.len(), 0);
+    }
+
+    #[test]
+    fn test_send_nft_calls_receiver_and_club_staking() {
+        let mut deps = mock_dependencies(&[]);
+        setup(deps.as_mut());
+        mint_club(deps.as_mut(), "CLUB001", "Owner001");
+
+        let payload = Binary::from(b"list".to_vec());
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("Owner001", &[]),
+            ExecuteMsg::SendNft {
+                contract: "marketplace11111".to_string(),
+                token_id: "CLUB001".to_string(),
+                msg: payload.clone(),
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[0],
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "marketplace11111".to_string(),
+                msg: Cw721ReceiveMsg {
+                    sender: "Owner001".to_string(),
+                    token_id: "CLUB001".to_string(),
+                    msg: payload,
+                }
+                .into_binary()
+                .unwrap(),
+                funds: vec![],
+            }))
+        );
+        if let CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) = &res.messages[1].msg {
+            assert_eq!(contract_addr, MINTER_ADDR);
+        } else {
+            panic!("expected a wasm message to the club staking contract");
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, BlockInfo};
+use cw721::{ContractInfoResponse, Expiration};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+
+pub const CONTRACT_INFO: Item<ContractInfoResponse> = Item::new("nft_info");
+
+/// The club staking contract, which mints the club tokens and
+/// follows their ownership
+pub const MINTER: Item<Addr> = Item::new("minter");
+
+pub const TOKEN_COUNT: Item<u64> = Item::new("num_tokens");
+
+/// Stored as (owner, operator)
+pub const OPERATORS: Map<(Addr, Addr), Expiration> = Map::new("operators");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenInfo {
+    /// The owner of the club
+    pub owner: Addr,
+    /// Approvals are stored here, as we clear them all upon transfer and cannot accumulate much
+    pub approvals: Vec<Approval>,
+    /// Name of the club this token stands for
+    pub name: String,
+    pub description: String,
+    pub image: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Approval {
+    /// Account that can transfer/send the token
+    pub spender: Addr,
+    /// When the Approval expires (maybe Expiration::never)
+    pub expires: Expiration,
+}
+
+impl Approval {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_expired(block)
+    }
+}
+
+pub struct TokenIndexes<'a> {
+    pub owner: MultiIndex<'a, (String, Vec<u8>), TokenInfo>,
+}
+
+impl<'a> IndexList<TokenInfo> for TokenIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenInfo>> + '_> {
+        let v: Vec<&dyn Index<TokenInfo>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+/// One token per club, keyed by the club name. Tokens can be
+/// looked up per owner through the owner index
+pub fn tokens<'a>() -> IndexedMap<'a, String, TokenInfo, TokenIndexes<'a>> {
+    let indexes = TokenIndexes {
+        owner: MultiIndex::new(
+            |d: &TokenInfo, k| (d.owner.to_string(), k),
+            "tokens",
+            "tokens__owner",
+        ),
+    };
+    IndexedMap::new("tokens", indexes)
+}
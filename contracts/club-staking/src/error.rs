@@ -31,6 +31,12 @@ pub enum ContractError {
         backtrace: Backtrace,
     },
 
+    #[error("Reward split must add up to 10000, found {total}")]
+    InvalidRewardSplit { total: Uint128 },
+
+    #[error("Number of winning clubs must be at least 1")]
+    InvalidWinnerCount {},
+
     #[error("Fees received = {received}uusd whereas required = {required}uusd")]
     InsufficientFees {
         received: Uint128,
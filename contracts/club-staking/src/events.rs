@@ -0,0 +1,173 @@
+use cosmwasm_std::{Event, Uint128};
+
+// Event types. wasmd prefixes custom events with "wasm-",
+// so these show up as wasm-club_reward, wasm-club_bought and so on
+pub const CLUB_REWARD_EVENT: &str = "club_reward";
+pub const CLUB_BOUGHT_EVENT: &str = "club_bought";
+pub const STAKE_EVENT: &str = "stake";
+pub const UNBOND_EVENT: &str = "unbond";
+pub const BOND_MATURED_EVENT: &str = "bond_matured";
+pub const RESTAKE_EVENT: &str = "restake";
+
+// Buckets of a club_reward event. The first three are handed out when
+// rewards are distributed, the *_claim ones are paid out to a wallet
+pub const CLUB_STAKERS_BUCKET: &str = "club_stakers";
+pub const WINNING_OWNER_BUCKET: &str = "winning_owner";
+pub const NON_WINNING_OWNER_BUCKET: &str = "non_winning_owner";
+pub const STAKER_CLAIM_BUCKET: &str = "staker_claim";
+pub const OWNER_CLAIM_BUCKET: &str = "owner_claim";
+pub const PREVIOUS_OWNER_CLAIM_BUCKET: &str = "previous_owner_claim";
+
+// Ways a club changes hands, the source of a club_bought event
+pub const BUY_SOURCE: &str = "buy";
+pub const ASSIGN_SOURCE: &str = "assign";
+pub const MARKET_SOURCE: &str = "market";
+pub const NFT_TRANSFER_SOURCE: &str = "nft_transfer";
+
+/// Reward of a bucket. There is no staker for the club_stakers bucket,
+/// it is shared by all the stakers of the club. There is no club for the
+/// previous_owner_claim bucket, it is claimed for all the clubs at once. The asset is the token contract
+/// address or the native denom
+pub fn club_reward_event(
+    club_name: Option<&str>,
+    staker: Option<&str>,
+    bucket: &str,
+    asset: &str,
+    amount: Uint128,
+) -> Event {
+    let mut event = Event::new(CLUB_REWARD_EVENT);
+    if let Some(club_name) = club_name {
+        event = event.add_attribute("club", club_name);
+    }
+    if let Some(staker) = staker {
+        event = event.add_attribute("staker", staker);
+    }
+    return event
+        .add_attribute("bucket", bucket)
+        .add_attribute("asset", asset)
+        .add_attribute("amount", amount.to_string());
+}
+
+/// Change of owner of a club. There is no seller for a club bought from the contract
+pub fn club_bought_event(
+    club_name: &str,
+    buyer: &str,
+    seller: Option<&str>,
+    source: &str,
+    amount: Uint128,
+) -> Event {
+    let mut event = Event::new(CLUB_BOUGHT_EVENT)
+        .add_attribute("club", club_name)
+        .add_attribute("buyer", buyer);
+    if let Some(seller) = seller {
+        event = event.add_attribute("seller", seller);
+    }
+    return event
+        .add_attribute("source", source)
+        .add_attribute("amount", amount.to_string());
+}
+
+pub fn stake_event(club_name: &str, staker: &str, amount: Uint128, auto_stake: bool) -> Event {
+    return Event::new(STAKE_EVENT)
+        .add_attribute("club", club_name)
+        .add_attribute("staker", staker)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("auto_stake", auto_stake.to_string());
+}
+
+/// Withdrawal of a stake. An immediate withdrawal pays out at once less the penalty,
+/// otherwise the amount is bonded
+pub fn unbond_event(
+    club_name: &str,
+    staker: &str,
+    amount: Uint128,
+    immediate: bool,
+    penalty: Uint128,
+) -> Event {
+    return Event::new(UNBOND_EVENT)
+        .add_attribute("club", club_name)
+        .add_attribute("staker", staker)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("immediate", immediate.to_string())
+        .add_attribute("penalty", penalty.to_string());
+}
+
+/// Bonded amount paid back to the staker
+pub fn bond_matured_event(club_name: &str, staker: &str, amount: Uint128) -> Event {
+    return Event::new(BOND_MATURED_EVENT)
+        .add_attribute("club", club_name)
+        .add_attribute("staker", staker)
+        .add_attribute("amount", amount.to_string());
+}
+
+/// Stake moved from one club to another
+pub fn restake_event(from_club: &str, to_club: &str, staker: &str, amount: Uint128) -> Event {
+    return Event::new(RESTAKE_EVENT)
+        .add_attribute("from_club", from_club)
+        .add_attribute("to_club", to_club)
+        .add_attribute("staker", staker)
+        .add_attribute("amount", amount.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(event: &Event) -> Vec<&str> {
+        return event.attributes.iter().map(|a| a.key.as_str()).collect();
+    }
+
+    #[test]
+    fn test_event_schema() {
+        let amount = Uint128::from(100u128);
+
+        let event = club_reward_event(
+            Some("CLUB001"),
+            Some("Staker001"),
+            STAKER_CLAIM_BUCKET,
+            "fury_token",
+            amount,
+        );
+        assert_eq!(event.ty, "club_reward");
+        assert_eq!(keys(&event), vec!["club", "staker", "bucket", "asset", "amount"]);
+        let event = club_reward_event(Some("CLUB001"), None, CLUB_STAKERS_BUCKET, "uusd", amount);
+        assert_eq!(keys(&event), vec!["club", "bucket", "asset", "amount"]);
+        assert_eq!(event.attributes[1].value, "club_stakers");
+        assert_eq!(event.attributes[2].value, "uusd");
+        assert_eq!(event.attributes[3].value, "100");
+        let event = club_reward_event(
+            None,
+            Some("Owner001"),
+            PREVIOUS_OWNER_CLAIM_BUCKET,
+            "fury_token",
+            amount,
+        );
+        assert_eq!(keys(&event), vec!["staker", "bucket", "asset", "amount"]);
+
+        let event = club_bought_event("CLUB001", "Owner002", Some("Owner001"), MARKET_SOURCE, amount);
+        assert_eq!(event.ty, "club_bought");
+        assert_eq!(keys(&event), vec!["club", "buyer", "seller", "source", "amount"]);
+        let event = club_bought_event("CLUB001", "Owner001", None, BUY_SOURCE, amount);
+        assert_eq!(keys(&event), vec!["club", "buyer", "source", "amount"]);
+
+        let event = stake_event("CLUB001", "Staker001", amount, true);
+        assert_eq!(event.ty, "stake");
+        assert_eq!(keys(&event), vec!["club", "staker", "amount", "auto_stake"]);
+        assert_eq!(event.attributes[3].value, "true");
+
+        let event = unbond_event("CLUB001", "Staker001", amount, false, Uint128::zero());
+        assert_eq!(event.ty, "unbond");
+        assert_eq!(
+            keys(&event),
+            vec!["club", "staker", "amount", "immediate", "penalty"]
+        );
+
+        let event = bond_matured_event("CLUB001", "Staker001", amount);
+        assert_eq!(event.ty, "bond_matured");
+        assert_eq!(keys(&event), vec!["club", "staker", "amount"]);
+
+        let event = restake_event("CLUB001", "CLUB002", "Staker001", amount);
+        assert_eq!(event.ty, "restake");
+        assert_eq!(keys(&event), vec!["from_club", "to_club", "staker", "amount"]);
+    }
+}
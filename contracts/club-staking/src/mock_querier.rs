@@ -0,0 +1,158 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, ContractResult, Decimal, Empty, Fraction, OwnedDeps,
+    Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw721::{Approval, Cw721QueryMsg, Expiration, OwnerOfResponse};
+use std::collections::HashMap;
+
+use crate::msg::ProxyQueryMsgs;
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier, which also answers the astro proxy price queries
+/// and the OwnerOf queries to the club NFT contract.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    astro_proxy_querier: AstroProxyQuerier,
+    club_nft_querier: ClubNftQuerier,
+}
+
+#[derive(Clone)]
+pub struct AstroProxyQuerier {
+    // uusd paid for one ufury
+    fury_price: Decimal,
+}
+
+impl Default for AstroProxyQuerier {
+    fn default() -> Self {
+        AstroProxyQuerier {
+            fury_price: Decimal::one(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ClubNftQuerier {
+    // club name to token owner
+    owners: HashMap<String, String>,
+    // club name to the spenders approved for the token
+    approvals: HashMap<String, Vec<String>>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr: _, msg }) => {
+                match from_binary(msg) {
+                    Ok(ProxyQueryMsgs::get_ust_equivalent_to_fury { fury_count }) => {
+                        let ust_count: Uint128 = fury_count * self.astro_proxy_querier.fury_price;
+                        SystemResult::Ok(ContractResult::Ok(to_binary(&ust_count).unwrap()))
+                    }
+                    Ok(ProxyQueryMsgs::get_fury_equivalent_to_ust { ust_count }) => {
+                        let fury_price = self.astro_proxy_querier.fury_price;
+                        let fury_count = ust_count
+                            .multiply_ratio(fury_price.denominator(), fury_price.numerator());
+                        SystemResult::Ok(ContractResult::Ok(to_binary(&fury_count).unwrap()))
+                    }
+                    Err(_) => match from_binary(msg) {
+                        Ok(Cw721QueryMsg::OwnerOf { token_id, .. }) => {
+                            match self.club_nft_querier.owners.get(&token_id) {
+                                Some(owner) => {
+                                    let approvals = self
+                                        .club_nft_querier
+                                        .approvals
+                                        .get(&token_id)
+                                        .cloned()
+                                        .unwrap_or_default()
+                                        .into_iter()
+                                        .map(|spender| Approval {
+                                            spender,
+                                            expires: Expiration::Never {},
+                                        })
+                                        .collect();
+                                    let owner_of = OwnerOfResponse {
+                                        owner: owner.clone(),
+                                        approvals,
+                                    };
+                                    SystemResult::Ok(ContractResult::Ok(
+                                        to_binary(&owner_of).unwrap(),
+                                    ))
+                                }
+                                None => SystemResult::Ok(ContractResult::Err(format!(
+                                    "No token for {}",
+                                    token_id
+                                ))),
+                            }
+                        }
+                        _ => SystemResult::Err(SystemError::InvalidRequest {
+                            error: "Unsupported wasm query".to_string(),
+                            request: msg.as_slice().into(),
+                        }),
+                    },
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            astro_proxy_querier: AstroProxyQuerier::default(),
+            club_nft_querier: ClubNftQuerier::default(),
+        }
+    }
+
+    // configure the uusd price of one ufury
+    pub fn with_fury_price(&mut self, fury_price: Decimal) {
+        self.astro_proxy_querier = AstroProxyQuerier { fury_price };
+    }
+
+    // configure the holder of a club token on the club NFT contract.
+    // As with a transfer, the approvals of the token are cleared
+    pub fn with_club_nft_owner(&mut self, club_name: &str, owner: &str) {
+        self.club_nft_querier
+            .owners
+            .insert(club_name.to_string(), owner.to_string());
+        self.club_nft_querier.approvals.remove(club_name);
+    }
+
+    // approve a spender for a club token on the club NFT contract
+    pub fn with_club_nft_approval(&mut self, club_name: &str, spender: &str) {
+        self.club_nft_querier
+            .approvals
+            .entry(club_name.to_string())
+            .or_default()
+            .push(spender.to_string());
+    }
+}
@@ -3,8 +3,14 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use cosmwasm_std::{Coin, Timestamp};
 use cw20::{Cw20ReceiveMsg};
+use cw0::Expiration;
+use cw721::Cw721ReceiveMsg;
+use terraswap::asset::AssetInfo;
 
-use crate::state::ClubStakingDetails;
+use crate::state::{
+    ClubBondingDetails, ClubOwnershipDetails, ClubStakingDetails, RewardSplit, StakeWeighting,
+    WinnerSelection,
+};
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 pub struct InstantiateMsg {
@@ -24,8 +30,25 @@ pub struct InstantiateMsg {
     pub transaction_fees: Uint128,
     ///Specified in percentage multiplied by 100, i.e. 100% = 10000 and 0.01% = 1
     pub control_fees: Uint128,
+    ///Penalty on withdrawing before the bonding duration is over.
+    ///Specified in percentage multiplied by 100, i.e. 100% = 10000 and 0.01% = 1
+    pub penalty_bps: Uint128,
+    /// Receives the early withdrawal penalty. The penalty is burnt when not set
+    pub penalty_treasury_wallet: Option<String>,
+    ///Cut of every club sale on the market, paid to the club_fee_collector_wallet.
+    ///Specified in percentage multiplied by 100, i.e. 100% = 10000 and 0.01% = 1
+    pub royalty_bps: Uint128,
+    /// Defaults to 78% all stakers, 19% winning club stakers, 1% winning owners, 2% other owners
+    pub reward_split: Option<RewardSplit>,
+    /// Defaults to the club with the largest increase in stake
+    pub winner_selection: Option<WinnerSelection>,
+    /// Defaults to the stake at the time of the distribution
+    pub stake_weighting: Option<StakeWeighting>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -42,6 +65,7 @@ pub enum ExecuteMsg {
         club_name: String,
         auto_stake: bool,
     },
+    /// Anyone can stake for the staker, the tokens are pulled from the sender
     StakeOnAClub {
         staker: String,
         club_name: String,
@@ -69,12 +93,133 @@ pub enum ExecuteMsg {
         amount: Uint128,
         immediate_withdrawal: bool,
     },
-    PeriodicallyRefundStakeouts {},
+    /// Admin only. Pays out the matured ones among up to `limit` bonds, starting after
+    /// the bond `start_after` = (club name, bonder address, bond id). The last bond
+    /// looked at is returned in the `last_club_name`, `last_bonder_address` and
+    /// `last_bond_id` attributes, to be passed as `start_after` in the next call
+    PeriodicallyRefundStakeouts {
+        start_after: Option<(String, String, u64)>,
+        limit: Option<u32>,
+    },
+    /// Moves part of the stake of the sender to another club at once, without bonding.
+    /// Restakes of a staker are at least restake_cooldown apart
+    RestakeToClub {
+        from_club: String,
+        to_club: String,
+        amount: Uint128,
+    },
+    /// Pays out the matured bonds of the sender in the given club
+    ClaimMaturedBonds {
+        club_name: String,
+    },
+    /// Allows the operator to withdraw and claim for the sender, like cw721 ApproveAll.
+    /// Whoever sends it, the tokens are paid to the staker or its payout recipient
+    ApproveAll {
+        operator: String,
+        /// defaults to never
+        expires: Option<Expiration>,
+    },
+    RevokeAll {
+        operator: String,
+    },
+    /// Has the withdrawals and rewards of the sender paid to the recipient.
+    /// Unset pays them to the sender again
+    SetPayoutRecipient {
+        recipient: Option<String>,
+    },
     CalculateAndDistributeRewards {},
     ClaimStakerRewards {
         staker: String,
         club_name: String,
     },
+    /// Claims the reward of the stake in one asset. Rewards in other assets than the
+    /// staking token can be claimed even without a stake in the club
+    ClaimStakerAssetRewards {
+        staker: String,
+        club_name: String,
+        asset: AssetInfo,
+    },
+    /// Adds the native coins sent along to the rewards of the next distribution.
+    /// Each denom has to be a sponsor asset. cw20 rewards are sent with the
+    /// IncreaseRewardAmount receive message instead
+    IncreaseRewardAmount {
+        reward_from: String,
+    },
+    /// Admin only. Changes how rewards are shared out from the next distribution onwards
+    UpdateRewardSplit {
+        reward_split: RewardSplit,
+        winner_selection: Option<WinnerSelection>,
+        stake_weighting: Option<StakeWeighting>,
+    },
+    /// Admin only. Only the fields that are set are changed
+    UpdateConfig {
+        astro_proxy_address: Option<String>,
+        club_fee_collector_wallet: Option<String>,
+        reward_periodicity: Option<u64>,
+        club_price: Option<Uint128>,
+        bonding_duration: Option<u64>,
+        owner_release_locking_duration: Option<u64>,
+        platform_fees_collector_wallet: Option<String>,
+        platform_fees: Option<Uint128>,
+        transaction_fees: Option<Uint128>,
+        control_fees: Option<Uint128>,
+        penalty_bps: Option<Uint128>,
+        penalty_treasury_wallet: Option<String>,
+        /// can only be set once
+        club_nft_address: Option<String>,
+        royalty_bps: Option<Uint128>,
+        restake_cooldown: Option<u64>,
+    },
+    /// Admin only. The new admin takes over once it sends AcceptAdmin
+    ProposeNewAdmin {
+        new_admin: String,
+    },
+    AcceptAdmin {},
+    /// Admin only. The address no longer pays platform fees
+    AddFeeExemptAddress {
+        address: String,
+    },
+    /// Admin only
+    RemoveFeeExemptAddress {
+        address: String,
+    },
+    /// Admin only. Rewards can be sponsored in the asset from then on
+    AddSponsorAsset {
+        asset: AssetInfo,
+    },
+    /// Admin only
+    RemoveSponsorAsset {
+        asset: AssetInfo,
+    },
+    /// Sent by the club NFT contract whenever the token of a club changes hands.
+    /// The new owner is read back from the NFT contract
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Admin only. Mints the token of a club bought before the club NFT contract was set
+    MintClubNft {
+        club_name: String,
+    },
+    /// Puts the club of the sender up for sale at the given price in FURY.
+    /// Buyers pay through ReceivedMsg::BuyListedClub. If the club has a token,
+    /// this contract must be approved on the club NFT contract to transfer it
+    ListClub {
+        club_name: String,
+        price: Uint128,
+        /// defaults to never
+        expires: Option<Expiration>,
+    },
+    CancelListing {
+        club_name: String,
+    },
+    /// Sells the club of the sender to the bidder at the bid amount. If the club
+    /// has a token, this contract must be approved on the club NFT contract to transfer it
+    AcceptBid {
+        club_name: String,
+        bidder: String,
+    },
+    /// Cancels the bid of the sender and refunds the amount held for it
+    CancelBid {
+        club_name: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -98,21 +243,54 @@ pub enum QueryMsg {
     ClubOwnershipDetailsForOwner {
         owner_address: String,
     },
+    /// Return type: AllClubOwnershipDetailsResponse.
     AllClubOwnershipDetails {
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
     AllPreviousClubOwnershipDetails {
     },
-    AllStakes {},
+    /// Stakes ordered by club and then staker. `start_after` is (club name, staker).
+    /// Return type: AllStakesResponse.
+    AllStakes {
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
     AllStakesForUser { 
         user_address: String,
     },
-    AllBonds {},
+    /// Bonds ordered by club. `start_after` is (club name, bonder address, bond id).
+    /// Return type: AllBondsResponse.
+    AllBonds {
+        start_after: Option<(String, String, u64)>,
+        limit: Option<u32>,
+    },
     ClubBondingDetailsForUser { 
         club_name: String,
         user_address: String,
     },
-    GetClubRankingByStakes {},
+    /// Clubs ranked by the increase in stake since the last distribution, then by total stake.
+    /// `start_after` is (rank, club name) of the last club seen.
+    /// Return type: ClubRankingResponse.
+    GetClubRankingByStakes {
+        start_after: Option<(u64, String)>,
+        limit: Option<u32>,
+    },
+    /// Aggregate stake of a club, without walking its stakers.
+    /// Return type: ClubTotalsResponse.
+    ClubTotals {
+        club_name: String,
+    },
     RewardAmount {},
+    /// Rewards waiting for the next distribution in each asset.
+    /// Return type: Vec<Asset>.
+    RewardAmounts {},
+    /// Claimable rewards of a stake in other assets than the staking token.
+    /// Return type: Vec<Asset>.
+    StakerAssetRewards {
+        staker: String,
+        club_name: String,
+    },
     QueryPlatformFees { 
         msg: Binary,
     },
@@ -120,11 +298,154 @@ pub enum QueryMsg {
         staker: String,
         club_name: String,
     },
+    Config {},
+    FeeExemptAddresses {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Assets rewards can be sponsored in, as token contract addresses and native denoms
+    SponsorAssets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Previews an immediate withdrawal of the given amount. Fails like the withdrawal
+    /// would when the staker has no stake in the club or not enough to cover the amount.
+    /// Return type: EarlyUnbondPenaltyResponse.
+    EarlyUnbondPenalty {
+        staker: String,
+        club_name: String,
+        amount: Uint128,
+    },
+    /// Clubs up for sale, ordered by club name.
+    /// Return type: Vec<ClubListing>.
+    ClubListings {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Bids on a club, ordered by bidder.
+    /// Return type: Vec<ClubBid>.
+    ClubBids {
+        club_name: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Fills and cancellations on the market, oldest first. `start_after` is a record id.
+    /// Return type: Vec<MarketRecord>.
+    MarketHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Operators approved by the staker, ordered by operator.
+    /// Return type: cw721::ApprovedForAllResponse.
+    ApprovedForAll {
+        staker: String,
+        /// unset or false filters out expired approvals
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Address the withdrawals and rewards of the staker are paid to.
+    /// Return type: String.
+    PayoutRecipient {
+        staker: String,
+    },
+    /// Outcome of a reward distribution. Epochs are numbered from 1.
+    /// Return type: EpochResult.
+    EpochResult {
+        epoch: u64,
+    },
+    /// Rewards of a staker in each epoch, oldest first. `start_after` is an epoch.
+    /// Only the last 100 epochs are kept.
+    /// Claimable rewards are rounded down per epoch.
+    /// Return type: StakerEpochRewardsResponse.
+    StakerEpochRewards {
+        staker: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// The `next_start_after` of each page is passed as `start_after` to get the
+/// following page. It is not set on the last page
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllStakesResponse {
+    pub stakes: Vec<ClubStakingDetails>,
+    pub next_start_after: Option<(String, String)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllBondsResponse {
+    pub bonds: Vec<ClubBondingDetails>,
+    pub next_start_after: Option<(String, String, u64)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllClubOwnershipDetailsResponse {
+    pub clubs: Vec<ClubOwnershipDetails>,
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClubRanking {
+    /// starts at 1
+    pub rank: u64,
+    pub club_name: String,
+    /// change in stake since the last distribution, can be negative
+    pub incremental_stake: String,
+    pub total_stake: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClubRankingResponse {
+    pub ranking: Vec<ClubRanking>,
+    /// number of clubs tied at the top
+    pub winners: u64,
+    pub next_start_after: Option<(u64, String)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClubTotalsResponse {
+    pub club_name: String,
+    pub total_staked: Uint128,
+    /// part of total_staked that compounds its rewards
+    pub auto_staked: Uint128,
+    /// total_staked when rewards were last distributed
+    pub staked_at_last_distribution: Uint128,
+}
+
+/// Reward of a staker in one club for one epoch
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerEpochReward {
+    pub epoch: u64,
+    pub club_name: String,
+    /// share of the stakers reward of the club
+    pub staking_reward: Uint128,
+    /// owner reward, when the staker owned the club
+    pub owner_reward: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerEpochRewardsResponse {
+    pub rewards: Vec<StakerEpochReward>,
+    pub next_start_after: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EarlyUnbondPenaltyResponse {
+    /// part of the amount covered by matured bonds - no penalty on it
+    pub matured_amount: Uint128,
+    pub penalty: Uint128,
+    /// what reaches the staker after the penalty
+    pub payout: Uint128,
 }
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ReceivedMsg {
     IncreaseRewardAmount(IncreaseRewardAmountCommand),
+    /// Buys a listed club. The amount sent must match the asking price
+    BuyListedClub(BuyListedClubCommand),
+    /// Bids the amount sent on a club
+    PlaceBid(PlaceBidCommand),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -132,6 +453,25 @@ pub struct IncreaseRewardAmountCommand {
     pub reward_from: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BuyListedClubCommand {
+    pub club_name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlaceBidCommand {
+    pub club_name: String,
+    /// defaults to never
+    pub expires: Option<Expiration>,
+}
+
+/// The part of the club NFT contract messages used here
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClubNftExecuteMsg {
+    Mint { token_id: String, owner: String },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum ProxyQueryMsgs {
     get_fury_equivalent_to_ust {
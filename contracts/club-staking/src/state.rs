@@ -1,8 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Timestamp, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw0::Expiration;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, Prefix, U64Key};
+use terraswap::asset::Asset;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -22,11 +24,124 @@ pub struct Config {
     pub transaction_fees: Uint128,
     ///Specified in percentage multiplied by 100, i.e. 100% = 10000 and 0.01% = 1
     pub control_fees: Uint128,
+    ///Penalty on withdrawing before the bonding duration is over.
+    ///Specified in percentage multiplied by 100, i.e. 100% = 10000 and 0.01% = 1
+    #[serde(default = "default_penalty_bps")]
+    pub penalty_bps: Uint128,
+    ///Receives the early withdrawal penalty. The penalty is burnt when not set
+    #[serde(default)]
+    pub penalty_treasury_wallet: Option<Addr>,
+    #[serde(default)]
+    pub reward_split: RewardSplit,
+    #[serde(default)]
+    pub winner_selection: WinnerSelection,
+    #[serde(default)]
+    pub stake_weighting: StakeWeighting,
+    ///Club NFT contract. Once set, each newly bought club gets a token and
+    ///the holder of the token is the owner of the club
+    #[serde(default)]
+    pub club_nft_address: Option<Addr>,
+    ///Cut of every club sale on the market, paid to the club_fee_collector_wallet.
+    ///Specified in percentage multiplied by 100, i.e. 100% = 10000 and 0.01% = 1
+    #[serde(default)]
+    pub royalty_bps: Uint128,
+    ///Seconds a staker has to wait between two restakes. No wait when zero
+    #[serde(default)]
+    pub restake_cooldown: u64,
+}
+
+fn default_penalty_bps() -> Uint128 {
+    // 10%
+    Uint128::from(1000u128)
+}
+
+/// How the reward of each period is shared out.
+/// Each part is specified in percentage multiplied by 100, i.e. 100% = 10000 and 0.01% = 1
+/// and the parts must add up to 10000
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardSplit {
+    /// shared by all stakers in proportion to their stake
+    pub all_stakers: Uint128,
+    /// shared by the stakers of the winning clubs in proportion to their stake in the club
+    pub winning_club_stakers: Uint128,
+    /// shared equally by the owners of the winning clubs
+    pub winning_owners: Uint128,
+    /// shared equally by the owners of the other clubs
+    pub non_winning_owners: Uint128,
+}
+
+impl Default for RewardSplit {
+    fn default() -> Self {
+        RewardSplit {
+            all_stakers: Uint128::from(7800u128),
+            winning_club_stakers: Uint128::from(1900u128),
+            winning_owners: Uint128::from(100u128),
+            non_winning_owners: Uint128::from(200u128),
+        }
+    }
+}
+
+/// Rule used to pick the winning clubs of a reward period
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WinnerSelection {
+    /// clubs with the largest increase in stake since the previous period, ties broken by total stake
+    IncrementalStake,
+    /// clubs with the largest total stake
+    TotalStake,
+    /// the given number of clubs with the largest total stake
+    TopN { count: u64 },
+}
+
+impl Default for WinnerSelection {
+    fn default() -> Self {
+        WinnerSelection::IncrementalStake
+    }
+}
+
+/// Stake of a club or a staker that counts when picking the winners and
+/// sharing out the reward of a period
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeWeighting {
+    /// the stake at the time the rewards are distributed
+    Instantaneous,
+    /// the stake averaged over the period, i.e. its stake-seconds divided by the length
+    /// of the period. A stake added just before the distribution hardly counts
+    TimeWeighted,
+}
+
+impl Default for StakeWeighting {
+    fn default() -> Self {
+        StakeWeighting::Instantaneous
+    }
 }
 
 pub const CONFIG_KEY: &str = "config";
 pub const CONFIG: Item<Config> = Item::new(CONFIG_KEY);
 
+/// Addresses that do not pay platform fees, managed by the admin.
+/// The key is the address, the value is always true
+pub const FEE_EXEMPT_ADDRESSES: Map<String, bool> = Map::new("fee_exempt_addresses");
+
+/// Assets other than the staking token that rewards can be sponsored in, managed by
+/// the admin. The key is the token contract address or the native denom, the value
+/// is always true
+pub const SPONSOR_ASSETS: Map<String, bool> = Map::new("sponsor_assets");
+
+/// Address proposed as the next admin, until it accepts
+pub const PENDING_ADMIN: Item<Addr> = Item::new("pending_admin");
+
+/// Operators allowed to withdraw and claim for a staker until the approval expires.
+/// The key is (staker, operator)
+pub const OPERATORS: Map<(String, String), Expiration> = Map::new("operators");
+
+/// Address a staker has its withdrawals and rewards paid to instead of itself
+pub const PAYOUT_RECIPIENTS: Map<String, Addr> = Map::new("payout_recipients");
+
+/// Time of the last restake of each staker, for the restake cooldown
+pub const LAST_RESTAKE: Map<String, Timestamp> = Map::new("last_restake");
+
 /// This is used for saving various vesting details
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 #[serde(rename_all = "snake_case")]
@@ -86,8 +201,55 @@ pub struct ClubStakingDetails {
 
     /// whether rewards are auto-staked or do they need to be claimed
     pub auto_stake: bool,
+
+    /// REWARD_PER_STAKE_ALL as of the last time rewards were settled into this stake
+    #[serde(default)]
+    pub reward_per_stake_all_checkpoint: Decimal,
+
+    /// reward_per_stake of the club as of the last time rewards were settled into this stake
+    #[serde(default)]
+    pub reward_per_stake_club_checkpoint: Decimal,
+
+    /// auto_stake_growth of the club as of the last time rewards were settled into this stake.
+    /// Zero for a stake that has never been settled
+    #[serde(default)]
+    pub auto_stake_growth_checkpoint: Decimal,
+
+    /// winner_auto_stake_growth of the club as of the last time rewards were settled into this stake
+    #[serde(default)]
+    pub winner_auto_stake_growth_checkpoint: Decimal,
+
+    /// Changes of the stake in its latest epoch, settled once that epoch is distributed
+    #[serde(default)]
+    pub stake_seconds: StakeSeconds,
+}
+
+/// How far the stake-seconds of an epoch are from holding the stake at the end of
+/// the epoch for the whole epoch. Changing the stake by an amount t seconds into the
+/// epoch adds amount * t to missed for an increase and to extra for a decrease
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct StakeSeconds {
+    pub epoch: u64,
+    pub missed: Uint128,
+    pub extra: Uint128,
+}
+
+/// Sum of the StakeSeconds of the stakes of a club in the epoch in progress,
+/// kept apart for the auto staked stakes as their rewards are compounded
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct ClubStakeSeconds {
+    pub epoch: u64,
+    pub missed: Uint128,
+    pub extra: Uint128,
+    pub auto_missed: Uint128,
+    pub auto_extra: Uint128,
 }
 
+pub const CLUB_STAKE_SECONDS: Map<String, ClubStakeSeconds> = Map::new("club_stake_seconds");
+
+/// Time the epoch in progress started, i.e. the time of the last reward distribution
+pub const EPOCH_START: Item<Timestamp> = Item::new("epoch_start");
+
 /// This is used for saving various bonding details for an unstaked club
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 #[serde(rename_all = "snake_case")]
@@ -108,19 +270,75 @@ pub struct ClubBondingDetails {
 
 // pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowance");
 
+/// Clubs whose token has been minted on the club NFT contract. Ownership of
+/// these clubs only changes by transferring the token
+pub const CLUB_NFT_MINTED: Map<String, bool> = Map::new("club_nft_minted");
+
 /// Map of clubs and its owners. the key is club name and the
 /// ClubOwnershipDetails will contain information about the owner
 pub const CLUB_OWNERSHIP_DETAILS: Map<String, ClubOwnershipDetails> =
     Map::new("club_ownership_details");
 
-/// Map of clubs and its stakers. the key is club name and the
-/// ClubStakingDetails will contain information about the stakers and amount staked
-pub const CLUB_STAKING_DETAILS: Map<String, Vec<ClubStakingDetails>> =
+/// Legacy layout of the staking ledger - one Vec of stakers per club.
+/// Only read by migrate to move the entries into club_staking_details()
+pub const LEGACY_CLUB_STAKING_DETAILS: Map<String, Vec<ClubStakingDetails>> =
     Map::new("club_staking_details");
 
-/// Map of clubs and its bonders. the key is club name and the
-/// ClubBondingDetails will contain information about the bonders and amount bonded
-pub const CLUB_BONDING_DETAILS: Map<String, Vec<ClubBondingDetails>> =
+pub struct ClubStakingIndexes<'a> {
+    // second element of the tuple is the primary key
+    pub staker: MultiIndex<'a, (String, Vec<u8>), ClubStakingDetails>,
+    pub club: MultiIndex<'a, (String, Vec<u8>), ClubStakingDetails>,
+}
+
+impl<'a> IndexList<ClubStakingDetails> for ClubStakingIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<ClubStakingDetails>> + '_> {
+        let v: Vec<&dyn Index<ClubStakingDetails>> = vec![&self.staker, &self.club];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Staking ledger of clubs and its stakers. The key is (club name, staker address)
+/// and the ClubStakingDetails will contain information about the amount staked.
+/// Stakes can also be looked up per staker or per club through the indexes
+pub fn club_staking_details<'a>(
+) -> IndexedMap<'a, (String, String), ClubStakingDetails, ClubStakingIndexes<'a>> {
+    let indexes = ClubStakingIndexes {
+        staker: MultiIndex::new(
+            |d: &ClubStakingDetails, k| (d.staker_address.clone(), k),
+            "club_stakes",
+            "club_stakes__staker",
+        ),
+        club: MultiIndex::new(
+            |d: &ClubStakingDetails, k| (d.club_name.clone(), k),
+            "club_stakes",
+            "club_stakes__club",
+        ),
+    };
+    IndexedMap::new("club_stakes", indexes)
+}
+
+/// Total amount staked in each club, kept in step with club_staking_details()
+/// so that ranking the clubs does not need to walk every stake
+pub const CLUB_STAKING_TOTALS: Map<String, Uint128> = Map::new("club_staking_totals");
+
+/// Bonds of the clubs, one entry per bond. The key is (club name, bonder address, bond id)
+/// and the ClubBondingDetails will contain the amount bonded
+pub const CLUB_BONDING_DETAILS: Map<(String, String, U64Key), ClubBondingDetails> =
+    Map::new(CLUB_BONDS_NAMESPACE);
+const CLUB_BONDS_NAMESPACE: &str = "club_bonds";
+
+/// Number of bonds ever made, the next bond id
+pub const BOND_COUNT: Item<u64> = Item::new("bond_count");
+
+/// The bonds of all the clubs in key order. Map::range only walks keys of
+/// up to two parts, so CLUB_BONDING_DETAILS is walked through its namespace
+pub fn all_club_bonds() -> Prefix<ClubBondingDetails> {
+    Prefix::new(CLUB_BONDS_NAMESPACE.as_bytes(), &[])
+}
+
+/// Legacy layout of the bonds - one Vec of bonds per club.
+/// Only read by migrate to move the entries into CLUB_BONDING_DETAILS
+pub const LEGACY_CLUB_BONDING_DETAILS: Map<String, Vec<ClubBondingDetails>> =
     Map::new("club_bonding_details");
 
 /// Map of previous owners and their reward points. the key is owner address and the
@@ -129,11 +347,209 @@ pub const CLUB_BONDING_DETAILS: Map<String, Vec<ClubBondingDetails>> =
 pub const CLUB_PREVIOUS_OWNER_DETAILS: Map<String, ClubPreviousOwnerDetails> =
     Map::new("club_previous_owner_details");
 
-pub const REWARD: Item<Uint128> = Item::new("staking_reward");
+/// Rewards waiting for the next distribution, one pool per asset. The key is the
+/// asset as displayed by AssetInfo - the token contract address or the native denom
+pub const REWARD: Map<String, Asset> = Map::new("staking_rewards");
+
+/// Reward pool from before rewards could be paid in other assets than the
+/// staking token. Only read by migrate to move it into REWARD
+pub const LEGACY_REWARD: Item<Uint128> = Item::new("staking_reward");
+
+/// Cumulative reward per staked token handed out of the pool shared by all stakers
+pub const REWARD_PER_STAKE_ALL: Item<Decimal> = Item::new("reward_per_stake_all");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClubRewardIndex {
+    /// Cumulative reward per staked token handed out of the pool for winning club stakers
+    pub reward_per_stake: Decimal,
+
+    /// What one auto staked token has grown to by compounding its rewards, starting at 1
+    pub auto_stake_growth: Decimal,
+
+    /// Part of the auto_stake_growth added by the winning club stakers reward. Kept apart
+    /// so that this reward and the one shared by all stakers are each rounded down
+    #[serde(default)]
+    pub winner_auto_stake_growth: Decimal,
+
+    /// Portion of the club total stake that belongs to auto staking stakers
+    pub auto_staked_amount: Uint128,
+}
+
+/// Reward accumulators of each club. Stakes are settled against these lazily
+/// so that distributing rewards does not need to touch every stake
+pub const CLUB_REWARD_INDEX: Map<String, ClubRewardIndex> = Map::new("club_reward_index");
 
 pub const CLUB_REWARD_NEXT_TIMESTAMP: Item<Timestamp> = Item::new("club_reward_next_timestamp");
 
+/// Reward accumulators of a club for an asset other than the staking token.
+/// These rewards are never auto staked
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct AssetRewardIndex {
+    /// Cumulative reward per staked token
+    pub reward_per_stake: Decimal,
+
+    /// Cumulative reward per staked token multiplied by the auto_stake_growth of the
+    /// club at the time. An auto staked amount divided by its growth checkpoint earns this
+    pub reward_per_auto_stake: Decimal,
+}
+
+/// The key is (club name, asset)
+pub const ASSET_REWARD_INDEX: Map<(String, String), AssetRewardIndex> =
+    Map::new("asset_reward_index");
+
+/// Reward of a stake in an asset other than the staking token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct StakerAssetReward {
+    pub reward_per_stake_checkpoint: Decimal,
+    pub reward_per_auto_stake_checkpoint: Decimal,
+    /// claimable reward amount
+    pub reward_amount: Uint128,
+}
+
+/// The key is (club name, staker address, asset). A stake without an entry for an asset
+/// has not changed since the asset was first distributed in the club, so it is settled
+/// from zero checkpoints
+pub const STAKER_ASSET_REWARDS: Map<(String, String, String), StakerAssetReward> =
+    Map::new("staker_asset_rewards");
+
 /// Snapshot of ranking by stakes
 pub const CLUB_STAKING_SNAPSHOT: Map<String, Uint128> =
     Map::new("club_staking_snapshot");
 
+/// The clubs in ranking order, the top club last. The key is (increase in stake
+/// since the last distribution and total stake as sortable bytes, club name) and
+/// the value the club name. Kept in step with CLUB_STAKING_TOTALS and CLUB_STAKING_SNAPSHOT
+pub const CLUB_RANKING: Map<(Vec<u8>, String), String> = Map::new("club_ranking");
+
+/// Ask of an owner selling the club on the market
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ClubListing {
+    pub club_name: String,
+    pub seller: String,
+    /// asking price in FURY
+    pub price: Uint128,
+    pub expires: Expiration,
+}
+
+/// At most one listing per club, keyed by club name
+pub const CLUB_LISTINGS: Map<String, ClubListing> = Map::new("club_listings");
+
+/// Bid on a club. The bid amount is held by this contract until the bid
+/// is accepted or cancelled
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ClubBid {
+    pub club_name: String,
+    pub bidder: String,
+    /// amount of FURY held for the bid
+    pub amount: Uint128,
+    pub expires: Expiration,
+}
+
+/// Bids keyed by (club name, bidder)
+pub const CLUB_BIDS: Map<(String, String), ClubBid> = Map::new("club_bids");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketAction {
+    ListingFilled,
+    ListingCancelled,
+    BidAccepted,
+    BidCancelled,
+}
+
+/// A fill or a cancellation on the market
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MarketRecord {
+    pub id: u64,
+    pub club_name: String,
+    pub action: MarketAction,
+    /// not set for cancelled bids
+    pub seller: Option<String>,
+    /// not set for cancelled listings
+    pub buyer: Option<String>,
+    pub price: Uint128,
+    /// part of the price paid to the club_fee_collector_wallet
+    pub royalty: Uint128,
+    pub timestamp: Timestamp,
+}
+
+/// Market fills and cancellations in the order they happened, keyed by record id
+pub const MARKET_HISTORY: Map<U64Key, MarketRecord> = Map::new("market_history");
+
+pub const MARKET_HISTORY_COUNT: Item<u64> = Item::new("market_history_count");
+
+
+/// Number of the last reward distribution. Stakes changed after it belong to the next epoch
+pub const EPOCH_COUNT: Item<u64> = Item::new("epoch_count");
+
+/// What a club got out of a reward distribution
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EpochClubResult {
+    pub club_name: String,
+    /// total stake of the club when the rewards were distributed
+    pub total_staked: Uint128,
+    /// total stake of the club at the previous distribution
+    pub previous_snapshot: Uint128,
+    pub winner: bool,
+    /// reward per staked token handed to the stakers of the club
+    pub reward_per_stake: Decimal,
+    /// auto_stake_growth of the club before the distribution
+    pub previous_auto_stake_growth: Decimal,
+    /// auto_stake_growth of the club after the distribution
+    pub auto_stake_growth: Decimal,
+    /// reward shared by the stakers of the club
+    pub stakers_reward: Uint128,
+    pub owner_address: String,
+    pub owner_reward: Uint128,
+    /// stake of the club the rewards were worked out on - the time weighted stake
+    /// when the epoch was distributed with time weighting, otherwise total_staked
+    #[serde(default)]
+    pub weighted_stake: Uint128,
+    /// reward per staked token in each of the other assets, keyed by the asset
+    #[serde(default)]
+    pub asset_reward_per_stake: Vec<(String, Decimal)>,
+}
+
+/// Outcome of one run of CalculateAndDistributeRewards
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EpochResult {
+    pub epoch: u64,
+    pub timestamp: Timestamp,
+    pub winners: Vec<String>,
+    /// number of clubs sharing the win
+    pub winner_count: u64,
+    pub total_reward: Uint128,
+    /// amount of each part of the reward split
+    pub all_stakers_reward: Uint128,
+    pub winning_club_stakers_reward: Uint128,
+    pub winning_owners_reward: Uint128,
+    pub non_winning_owners_reward: Uint128,
+    /// rounding remainder left in the reward pool
+    pub undistributed_reward: Uint128,
+    /// rewards distributed in other assets than the staking token
+    #[serde(default)]
+    pub sponsored_rewards: Vec<Asset>,
+    /// length of the epoch in seconds when it was distributed with time weighting, otherwise 0
+    #[serde(default)]
+    pub time_weighted_duration: u64,
+    /// in the order of the ranking, winners first
+    pub clubs: Vec<EpochClubResult>,
+}
+
+pub const EPOCH_RESULTS: Map<U64Key, EpochResult> = Map::new("epoch_results");
+
+/// The part of a stake that decides its share of an epoch reward
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct StakeCheckpoint {
+    pub staked_amount: Uint128,
+    pub auto_stake: bool,
+    pub auto_stake_growth_checkpoint: Decimal,
+    #[serde(default)]
+    pub stake_seconds: StakeSeconds,
+}
+
+/// Stake of each staker as last changed in an epoch. The key is
+/// (staker address, club name, epoch). A stake takes part in the distribution
+/// closing its epoch and in the following ones until it changes again
+pub const STAKE_HISTORY: Map<(String, String, U64Key), StakeCheckpoint> =
+    Map::new("stake_history");
@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError, StdResult,
+    Uint128,
 };
 
 #[cfg(not(feature = "library"))]
@@ -9,18 +10,22 @@ use cw2::set_contract_version;
 
 use crate::error::ContractError;
 use crate::execute::{
-    cancel_game, claim_refund, claim_reward, create_pool, game_pool_bid_submit,
-    game_pool_reward_distribute, lock_game, received_message, save_team_details,
-    set_platform_fee_wallets, set_pool_type_params,
+    bid_swap_reply, cancel_game, claim_rake, claim_refund, claim_reward, create_game, create_pool, game_pool_bid_submit,
+    game_pool_reward_distribute, game_pool_type_bid_submit, import_teams, lock_game, received_message,
+    set_platform_fee_wallets, set_pool_type_params, set_pool_type_payout_table, set_price_source,
+    validate_price_source,
 };
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::query::{
-    get_team_count_for_user_in_pool_type, query_all_pool_type_details, query_all_pools_in_game,
-    query_all_teams, query_game_details, query_game_result, query_pool_collection,
+    get_team_count_for_user_in_pool_type, query_all_games, query_all_pool_type_details,
+    query_all_pools_in_game, query_all_teams, query_game_details, query_game_result, query_pool_collection,
     query_pool_details, query_pool_team_details, query_pool_type_details, query_refund,
-    query_reward, query_team_details,
+    query_rake_ledger, query_reward, query_team_details,
+};
+use crate::state::{
+    pool_details, pool_team_details, Config, PoolDetails, GameDetails, PriceSource, GameResult, CONFIG, GAME_DETAILS, GAME_RESULT_DUMMY,
+    LEGACY_CONFIG, LEGACY_POOL_TEAM_DETAILS,
 };
-use crate::state::{Config, GameDetails, GameResult, CONFIG, GAME_DETAILS, GAME_RESULT_DUMMY};
 
 // version info for migration info
 pub const CONTRACT_NAME: &str = "crates.io:gaming-pool";
@@ -53,6 +58,12 @@ pub const GAME_COMPLETED: u64 = 4u64;
 pub const HUNDRED_PERCENT: u128 = 10000u128;
 pub const NINETY_NINE_NINE_PERCENT: u128 = 9990u128;
 
+// Spread allowed on the swap of a bid when the gamer sets none, and the most a gamer can allow
+pub const DEFAULT_MAX_SPREAD_PERMILLE: u64 = 5u64;
+pub const MAX_SPREAD_LIMIT_PERCENT: u64 = 50u64;
+
+pub const BID_SWAP_REPLY_ID: u64 = 1u64;
+
 pub const DUMMY_TEAM_ID: &str = "DUMMY_TEAM_ID";
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -73,7 +84,7 @@ pub fn instantiate(
         astro_proxy_address: deps.api.addr_validate(&msg.astro_proxy_address)?,
         platform_fee: msg.platform_fee,
         transaction_fee: msg.transaction_fee,
-        game_id: msg.game_id.clone(),
+        price_source: validate_price_source(deps.api, msg.price_source.unwrap_or_default())?,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -84,7 +95,7 @@ pub fn instantiate(
         &main_address,
         &GameResult {
             gamer_address: DUMMY_WALLET.to_string(),
-            game_id: msg.game_id.clone(),
+            game_id: String::default(),
             team_id: DUMMY_TEAM_ID.to_string(),
             team_rank: INITIAL_TEAM_RANK,
             team_points: INITIAL_TEAM_POINTS,
@@ -92,15 +103,6 @@ pub fn instantiate(
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
         },
     )?;
-
-    GAME_DETAILS.save(
-        deps.storage,
-        msg.game_id.clone(),
-        &GameDetails {
-            game_id: msg.game_id.clone(),
-            game_status: GAME_POOL_OPEN,
-        },
-    )?;
     Ok(Response::default())
 }
 
@@ -116,6 +118,7 @@ pub fn execute(
         ExecuteMsg::SetPlatformFeeWallets { wallet_percentages } => {
             set_platform_fee_wallets(deps, info, wallet_percentages)
         }
+        ExecuteMsg::SetPriceSource { price_source } => set_price_source(deps, info, price_source),
         ExecuteMsg::SetPoolTypeParams {
             pool_type,
             pool_fee,
@@ -134,54 +137,70 @@ pub fn execute(
             max_teams_for_gamer,
             wallet_percentages,
         ),
-        ExecuteMsg::CancelGame {} => cancel_game(deps, env, info),
-        ExecuteMsg::LockGame {} => lock_game(deps, env, info),
-        ExecuteMsg::CreatePool { pool_type } => create_pool(deps, env, info, pool_type),
+        ExecuteMsg::SetPoolTypePayoutTable {
+            pool_type,
+            payout_table,
+        } => set_pool_type_payout_table(deps, info, pool_type, payout_table),
+        ExecuteMsg::CreateGame {
+            game_id,
+            lock_at,
+            settle_deadline,
+        } => create_game(deps, env, info, game_id, lock_at, settle_deadline),
+        ExecuteMsg::CancelGame { game_id } => cancel_game(deps, env, info, game_id),
+        ExecuteMsg::LockGame { game_id } => lock_game(deps, env, info, game_id),
+        ExecuteMsg::CreatePool { game_id, pool_type } => {
+            create_pool(deps, env, info, game_id, pool_type)
+        }
         ExecuteMsg::ClaimReward { gamer } => claim_reward(deps, info, gamer, env),
         ExecuteMsg::ClaimRefund { gamer } => claim_refund(deps, info, gamer, env),
+        ExecuteMsg::ClaimRake { wallet } => claim_rake(deps, wallet),
         ExecuteMsg::GamePoolRewardDistribute {
+            game_id,
             pool_id,
             game_winners,
-        } => game_pool_reward_distribute(deps, env, info, pool_id, game_winners),
-        ExecuteMsg::SaveTeamDetails {
+        } => game_pool_reward_distribute(deps, env, info, game_id, pool_id, game_winners),
+        ExecuteMsg::ImportTeams { teams } => import_teams(deps, env, info, teams),
+        ExecuteMsg::GamePoolBidSubmitCommand {
             gamer,
-            pool_id,
-            team_id,
-            game_id,
             pool_type,
-            reward_amount,
-            claimed_reward,
-            refund_amount,
-            claimed_refund,
-            team_points,
-            team_rank,
-        } => save_team_details(
-            deps.storage,
-            env,
-            gamer,
             pool_id,
             team_id,
-            game_id,
-            pool_type,
-            reward_amount,
-            claimed_reward,
-            refund_amount,
-            claimed_refund,
-            team_points,
-            team_rank,
+            amount,
+            max_spread,
+            belief_price,
+            min_receive,
+        } => game_pool_bid_submit(
+            deps, env, info, gamer, pool_type, pool_id, team_id, amount, max_spread, belief_price,
+            min_receive,
         ),
-        ExecuteMsg::GamePoolBidSubmitCommand {
+        ExecuteMsg::GamePoolTypeBidSubmitCommand {
             gamer,
+            game_id,
             pool_type,
-            pool_id,
             team_id,
             amount,
-        } => game_pool_bid_submit(
-            deps, env, info, gamer, pool_type, pool_id, team_id, amount, false,
+            max_spread,
+            belief_price,
+            min_receive,
+        } => game_pool_type_bid_submit(
+            deps, env, info, gamer, game_id, pool_type, team_id, amount, max_spread, belief_price,
+            min_receive,
         ),
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
+    match reply.id {
+        BID_SWAP_REPLY_ID => bid_swap_reply(deps, env),
+        _ => {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: format!("Unknown reply id {}", reply.id),
+            }));
+        }
+    }
+}
+
 // This is the safe way of contract migration
 // We can add expose specific state properties to
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -196,8 +215,84 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
     // if ver.version >= CONTRACT_VERSION.to_string() {
     //     return Err(StdError::generic_err("Cannot upgrade from a newer version").into());
     // }
+
+    // An instance that ran a single game keeps it as one of its games.
+    // Its pools already carry the game id
+    if let Ok(legacy_config) = LEGACY_CONFIG.load(deps.storage) {
+        if GAME_DETAILS.may_load(deps.storage, legacy_config.game_id.clone())?.is_none() {
+            GAME_DETAILS.save(
+                deps.storage,
+                legacy_config.game_id.clone(),
+                &GameDetails {
+                    game_id: legacy_config.game_id.clone(),
+                    game_status: GAME_POOL_OPEN,
+                    lock_at: None,
+                    settle_deadline: None,
+                },
+            )?;
+        }
+        CONFIG.save(
+            deps.storage,
+            &Config {
+                admin_address: legacy_config.admin_address,
+                minting_contract_address: legacy_config.minting_contract_address,
+                platform_fees_collector_wallet: legacy_config.platform_fees_collector_wallet,
+                astro_proxy_address: legacy_config.astro_proxy_address,
+                platform_fee: legacy_config.platform_fee,
+                transaction_fee: legacy_config.transaction_fee,
+                price_source: PriceSource::AstroProxy {},
+            },
+        )?;
+    }
+
+    // Teams move from one list per pool to a row per team
+    let legacy_pools: Vec<String> = LEGACY_POOL_TEAM_DETAILS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|k| String::from_utf8(k).unwrap())
+        .collect();
+    let mut migrated_teams = 0u64;
+    for pool_id in legacy_pools {
+        let legacy_teams = LEGACY_POOL_TEAM_DETAILS.load(deps.storage, pool_id.clone())?;
+        for team in legacy_teams {
+            let key = (pool_id.clone(), team.gamer_address.clone(), team.team_id.clone());
+            let mut migrated_team = team.clone();
+            // the list did not prevent a gamer bidding twice with the same team id.
+            // Fold such entries together so that nothing unclaimed is lost
+            if let Some(existing_team) = pool_team_details().may_load(deps.storage, key.clone())? {
+                migrated_team = existing_team;
+                if team.claimed_reward == UNCLAIMED_REWARD {
+                    if migrated_team.claimed_reward != UNCLAIMED_REWARD {
+                        migrated_team.reward_amount = Uint128::zero();
+                        migrated_team.claimed_reward = UNCLAIMED_REWARD;
+                    }
+                    migrated_team.reward_amount += team.reward_amount;
+                }
+                if team.claimed_refund == UNCLAIMED_REFUND {
+                    if migrated_team.claimed_refund != UNCLAIMED_REFUND {
+                        migrated_team.refund_amount = Uint128::zero();
+                        migrated_team.claimed_refund = UNCLAIMED_REFUND;
+                    }
+                    migrated_team.refund_amount += team.refund_amount;
+                }
+            }
+            pool_team_details().save(deps.storage, key, &migrated_team)?;
+            migrated_teams += 1;
+        }
+        LEGACY_POOL_TEAM_DETAILS.remove(deps.storage, pool_id);
+    }
+
+    // Pools saved before the pool indexes existed get indexed
+    let pools: Vec<(String, PoolDetails)> = pool_details()
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, pool)| (pool.pool_id.clone(), pool)))
+        .collect::<StdResult<_>>()?;
+    for (pool_id, pool) in pools {
+        pool_details().save(deps.storage, pool_id, &pool)?;
+    }
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("migrated_teams", migrated_teams.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -212,18 +307,27 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::AllPoolTypeDetails {} => to_binary(&query_all_pool_type_details(deps.storage)?),
         QueryMsg::AllTeams {} => to_binary(&query_all_teams(deps.storage)?),
-        QueryMsg::QueryReward { gamer } => to_binary(&query_reward(deps.storage, gamer)?),
-        QueryMsg::QueryRefund { gamer } => to_binary(&query_refund(deps.storage, gamer)?),
+        QueryMsg::QueryReward { gamer, game_id } => {
+            to_binary(&query_reward(deps.storage, gamer, game_id)?)
+        }
+        QueryMsg::QueryRefund { gamer, game_id } => {
+            to_binary(&query_refund(deps.storage, gamer, game_id, _env.block.time)?)
+        }
         QueryMsg::QueryGameResult {
             gamer,
             pool_id,
             team_id,
         } => to_binary(&query_game_result(deps, gamer, pool_id, team_id)?),
-        QueryMsg::GameDetails {} => to_binary(&query_game_details(deps.storage)?),
+        QueryMsg::GameDetails { game_id } => {
+            to_binary(&query_game_details(deps.storage, game_id, _env.block.time)?)
+        }
+        QueryMsg::AllGames {} => to_binary(&query_all_games(deps.storage, _env.block.time)?),
         QueryMsg::PoolTeamDetailsWithTeamId { pool_id, team_id } => {
             to_binary(&query_team_details(deps.storage, pool_id, team_id)?)
         }
-        QueryMsg::AllPoolsInGame {} => to_binary(&query_all_pools_in_game(deps.storage)?),
+        QueryMsg::AllPoolsInGame { game_id } => {
+            to_binary(&query_all_pools_in_game(deps.storage, game_id)?)
+        }
         QueryMsg::PoolCollection { pool_id } => {
             to_binary(&query_pool_collection(deps.storage, pool_id)?)
         }
@@ -237,5 +341,6 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             game_id,
             pool_type,
         )?),
+        QueryMsg::RakeLedger {} => to_binary(&query_rake_ledger(deps.storage)?),
     }
 }
@@ -1,25 +1,28 @@
 use std::ops::Add;
 
 use astroport::asset::{Asset, AssetInfo};
+use astroport::oracle::QueryMsg as OracleQueryMsg;
 use astroport::pair::ExecuteMsg as AstroPortExecute;
-use cosmwasm_std::{BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, from_binary,
-                   MessageInfo, Order, Response, StdError, StdResult,
-                   Storage, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::{Api, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, from_binary,
+                   MessageInfo, Order, QuerierWrapper, Response, StdError, StdResult,
+                   Storage, SubMsg, Timestamp, to_binary, Uint128, WasmMsg};
 
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
-use crate::contract::{CLAIMED_REFUND, CLAIMED_REWARD, DUMMY_WALLET, GAME_CANCELLED,
-                      GAME_COMPLETED, GAME_POOL_CLOSED, GAME_POOL_OPEN, HUNDRED_PERCENT,
+use crate::contract::{BID_SWAP_REPLY_ID, CLAIMED_REFUND, CLAIMED_REWARD, DEFAULT_MAX_SPREAD_PERMILLE,
+                      DUMMY_WALLET, GAME_CANCELLED, GAME_COMPLETED, MAX_SPREAD_LIMIT_PERCENT, GAME_POOL_CLOSED, GAME_POOL_OPEN, HUNDRED_PERCENT,
                       INITIAL_REFUND_AMOUNT, INITIAL_REWARD_AMOUNT, INITIAL_TEAM_POINTS,
                       INITIAL_TEAM_RANK, NINETY_NINE_NINE_PERCENT, REWARDS_DISTRIBUTED,
                       REWARDS_NOT_DISTRIBUTED, UNCLAIMED_REFUND, UNCLAIMED_REWARD};
 use crate::ContractError;
-use crate::msg::{ProxyQueryMsgs, QueryMsgSimulation, ReceivedMsg};
-use crate::query::{get_team_count_for_user_in_pool_type, query_pool_details, query_pool_type_details};
-use crate::state::{CONFIG, CONTRACT_POOL_COUNT, FeeDetails, GAME_DETAILS, GameDetails,
-                   GameResult, PLATFORM_WALLET_PERCENTAGES, POOL_DETAILS,
-                   POOL_TEAM_DETAILS, POOL_TYPE_DETAILS, PoolDetails, PoolTeamDetails,
-                   PoolTypeDetails, WalletPercentage, WalletTransferDetails};
+use crate::msg::{ImportedTeam, ProxyQueryMsgs, QueryMsgSimulation, ReceivedMsg};
+use crate::query::{game_status_at, get_team_count_for_user_in_pool_type, query_all_pools_in_game,
+                   query_gamer_team_count_in_pool, query_gamer_teams, query_pool_details, query_pool_teams,
+                   query_pool_type_details, query_unsettled_pool_refund};
+use crate::state::{Config, CONFIG, CONTRACT_POOL_COUNT, FeeDetails, GAME_DETAILS, GameDetails,
+                   GameResult, PENDING_BID_SWAP, PendingBidSwap, PLATFORM_WALLET_PERCENTAGES, pool_details,
+                   POOL_TYPE_DETAILS, PoolDetails, PoolTeamDetails, PriceSource,
+                   PoolTypeDetails, RAKE_LEDGER, RakeLedgerEntry, WalletPercentage, pool_team_details};
 
 pub fn received_message(
     deps: DepsMut,
@@ -39,8 +42,88 @@ pub fn received_message(
             gpbsc.pool_id,
             gpbsc.team_id,
             amount,
-            false,
+            gpbsc.max_spread,
+            gpbsc.belief_price,
+            gpbsc.min_receive,
         ),
+        ReceivedMsg::GamePoolTypeBidSubmit(gptbsc) => game_pool_type_bid_submit(
+            deps,
+            env,
+            info,
+            gptbsc.gamer,
+            gptbsc.game_id,
+            gptbsc.pool_type,
+            gptbsc.team_id,
+            amount,
+            gptbsc.max_spread,
+            gptbsc.belief_price,
+            gptbsc.min_receive,
+        ),
+    }
+}
+
+pub fn set_price_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    price_source: PriceSource,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::Unauthorized {
+            invoker: info.sender.to_string(),
+        });
+    }
+    config.price_source = validate_price_source(deps.api, price_source)?;
+    CONFIG.save(deps.storage, &config)?;
+    return Ok(Response::new().add_attribute("action", "set_price_source"));
+}
+
+pub fn validate_price_source(api: &dyn Api, price_source: PriceSource) -> StdResult<PriceSource> {
+    match price_source {
+        PriceSource::Fixed { fury_per_ust } => {
+            if fury_per_ust.is_zero() {
+                return Err(StdError::GenericErr {
+                    msg: String::from("Fixed price must not be zero"),
+                });
+            }
+            return Ok(PriceSource::Fixed { fury_per_ust });
+        }
+        PriceSource::Twap { oracle_address } => {
+            return Ok(PriceSource::Twap {
+                oracle_address: api.addr_validate(oracle_address.as_str())?,
+            });
+        }
+        PriceSource::AstroProxy {} => {
+            return Ok(PriceSource::AstroProxy {});
+        }
+    }
+}
+
+/// The FURY to bid for a pool fee of ust_count, priced by the configured source
+pub fn query_fury_equivalent_to_ust(
+    querier: &QuerierWrapper,
+    config: &Config,
+    ust_count: Uint128,
+) -> StdResult<Uint128> {
+    match config.price_source.clone() {
+        PriceSource::AstroProxy {} => {
+            return querier.query_wasm_smart(
+                config.astro_proxy_address.clone(),
+                &ProxyQueryMsgs::get_fury_equivalent_to_ust { ust_count },
+            );
+        }
+        PriceSource::Fixed { fury_per_ust } => {
+            return Ok(ust_count * fury_per_ust);
+        }
+        PriceSource::Twap { oracle_address } => {
+            return querier.query_wasm_smart(
+                oracle_address,
+                &OracleQueryMsg::Consult {
+                    token: AssetInfo::NativeToken { denom: "uusd".to_string() },
+                    amount: ust_count,
+                },
+            );
+        }
     }
 }
 
@@ -56,6 +139,23 @@ pub fn set_platform_fee_wallets(
         });
     }
 
+    // Wallets are updated by name, so count the ones not being replaced too
+    let mut total_percentage: u32 = wallet_percentages.iter().map(|wp| wp.percentage).sum();
+    let existing_wallets: Vec<WalletPercentage> = PLATFORM_WALLET_PERCENTAGES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, wallet)| wallet))
+        .collect::<StdResult<_>>()?;
+    for wallet in existing_wallets {
+        if !wallet_percentages.iter().any(|wp| wp.wallet_name == wallet.wallet_name) {
+            total_percentage += wallet.percentage;
+        }
+    }
+    if total_percentage > 100 {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Platform wallet percentages exceed 100"),
+        }));
+    }
+
     for wp in wallet_percentages {
         PLATFORM_WALLET_PERCENTAGES.save(
             deps.storage,
@@ -97,6 +197,14 @@ pub fn set_pool_type_params(
         None => {}
     };
 
+    // The rake percentages are of the pool collection, so they must leave a prize
+    let total_percentage: u32 = wallet_percentages.iter().map(|wp| wp.percentage).sum();
+    if total_percentage >= 100 {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Rake percentages must be below 100"),
+        }));
+    }
+
     let mut rake_list: Vec<WalletPercentage> = Vec::new();
     for wp in wallet_percentages {
         rake_list.push(wp);
@@ -111,19 +219,110 @@ pub fn set_pool_type_params(
             max_teams_for_pool: max_teams_for_pool,
             max_teams_for_gamer: max_teams_for_gamer,
             rake_list: rake_list,
+            // only set by SetPoolTypePayoutTable, which is kept as the
+            // params of a pool type cannot be set again
+            payout_table: vec![],
         },
     )?;
     return Ok(Response::default());
 }
 
-pub fn cancel_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+pub fn set_pool_type_payout_table(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool_type: String,
+    payout_table: Vec<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::Unauthorized {
+            invoker: info.sender.to_string(),
+        });
+    }
+    let mut pool_type_details = query_pool_type_details(deps.storage, pool_type.clone())?;
+
+    // Whatever the table does not pay out goes to the rake wallets
+    let total_percentage: u32 = payout_table.iter().sum();
+    if total_percentage > 100 {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Payout table percentages exceed 100"),
+        }));
+    }
+    if payout_table.windows(2).any(|pair| pair[1] > pair[0]) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Payout table percentages must not increase with rank"),
+        }));
+    }
+
+    pool_type_details.payout_table = payout_table;
+    POOL_TYPE_DETAILS.save(deps.storage, pool_type.clone(), &pool_type_details)?;
+    return Ok(Response::new()
+        .add_attribute("action", "set_pool_type_payout_table")
+        .add_attribute("pool_type", pool_type));
+}
+
+pub fn create_game(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+    lock_at: Option<Timestamp>,
+    settle_deadline: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::Unauthorized {
+            invoker: info.sender.to_string(),
+        });
+    }
+    let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
+    if gd.is_some() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Game already exists"),
+        }));
+    }
+    if let Some(lock_at) = lock_at {
+        if lock_at <= env.block.time {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Game must be open for bidding until a future time"),
+            }));
+        }
+    }
+    if let Some(settle_deadline) = settle_deadline {
+        if settle_deadline <= lock_at.unwrap_or(env.block.time) {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Settle deadline must be after the game is locked"),
+            }));
+        }
+    }
+
+    GAME_DETAILS.save(
+        deps.storage,
+        game_id.clone(),
+        &GameDetails {
+            game_id: game_id.clone(),
+            game_status: GAME_POOL_OPEN,
+            lock_at: lock_at,
+            settle_deadline: settle_deadline,
+        },
+    )?;
+    return Ok(Response::new()
+        .add_attribute("game_id", game_id.clone())
+        .add_attribute("game_status", "GAME_POOL_OPEN".to_string()));
+}
+
+pub fn cancel_game(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    game_id: String,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if info.sender != config.admin_address {
         return Err(ContractError::Unauthorized {
             invoker: info.sender.to_string(),
         });
     }
-    let game_id = config.game_id;
 
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
@@ -154,17 +353,19 @@ pub fn cancel_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Respon
         &GameDetails {
             game_id: game_id.clone(),
             game_status: GAME_CANCELLED,
+            lock_at: game.lock_at,
+            settle_deadline: game.settle_deadline,
         },
     )?;
 
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
+    // Get all pools of the game
+    let all_pools: Vec<String> = query_all_pools_in_game(deps.storage, game_id.clone())?
+        .into_iter()
+        .map(|pool| pool.pool_id)
         .collect();
     for pool_id in all_pools {
         let pool;
-        let pd = POOL_DETAILS.may_load(deps.storage, pool_id.clone())?;
+        let pd = pool_details().may_load(deps.storage, pool_id.clone())?;
         match pd {
             Some(pd) => {
                 pool = pd;
@@ -187,33 +388,29 @@ pub fn cancel_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Respon
                 }));
             }
         };
+        // The teams of a pool already distributed keep their rewards
+        if pool.rewards_distributed == REWARDS_DISTRIBUTED {
+            continue;
+        }
         let refund_amount = pool_type.pool_fee;
 
         // Get the existing teams for this pool
-        // let mut teams = Vec::new();
-        let all_teams = POOL_TEAM_DETAILS.may_load(deps.storage, pool_id.clone())?;
-        match all_teams {
-            Some(some_teams) => {
-                let teams = some_teams;
-                let mut updated_teams: Vec<PoolTeamDetails> = Vec::new();
-                for team in teams {
-                    // No transfer to be done to the gamers. Just update their refund amounts.
-                    // They have to come and collect their refund
-                    // In case of refund due to lock_game min_team_count not met for the pool_type
-                    let mut updated_team = team.clone();
-                    if updated_team.refund_amount == Uint128::zero() {
-                        updated_team.refund_amount = refund_amount;
-                        updated_team.claimed_refund = UNCLAIMED_REFUND;
-                        println!(
-                            "refund for {:?} is {:?}",
-                            team.team_id, updated_team.refund_amount
-                        );
-                    }
-                    updated_teams.push(updated_team);
-                }
-                POOL_TEAM_DETAILS.save(deps.storage, pool_id.clone(), &updated_teams)?;
+        let teams = query_pool_teams(deps.storage, pool_id.clone())?;
+        for team in teams {
+            // No transfer to be done to the gamers. Just update their refund amounts.
+            // They have to come and collect their refund
+            // In case of refund due to lock_game min_team_count not met for the pool_type
+            if team.refund_amount != Uint128::zero() {
+                continue;
             }
-            None => {}
+            let mut updated_team = team.clone();
+            updated_team.refund_amount = refund_amount;
+            updated_team.claimed_refund = UNCLAIMED_REFUND;
+            println!(
+                "refund for {:?} is {:?}",
+                team.team_id, updated_team.refund_amount
+            );
+            save_pool_team(deps.storage, &updated_team)?;
         }
     }
     return Ok(Response::new()
@@ -221,14 +418,18 @@ pub fn cancel_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Respon
         .add_attribute("game_status", "GAME_CANCELLED".to_string()));
 }
 
-pub fn lock_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+pub fn lock_game(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    game_id: String,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if info.sender != config.admin_address {
         return Err(ContractError::Unauthorized {
             invoker: info.sender.to_string(),
         });
     }
-    let game_id = config.game_id;
 
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
@@ -254,17 +455,19 @@ pub fn lock_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response
         &GameDetails {
             game_id: game_id.clone(),
             game_status: GAME_POOL_CLOSED,
+            lock_at: game.lock_at,
+            settle_deadline: game.settle_deadline,
         },
     )?;
 
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
+    // Get all pools of the game
+    let all_pools: Vec<String> = query_all_pools_in_game(deps.storage, game_id.clone())?
+        .into_iter()
+        .map(|pool| pool.pool_id)
         .collect();
     for pool_id in all_pools {
         let pool;
-        let pd = POOL_DETAILS.may_load(deps.storage, pool_id.clone())?;
+        let pd = pool_details().may_load(deps.storage, pool_id.clone())?;
         match pd {
             Some(pd) => {
                 pool = pd;
@@ -293,27 +496,18 @@ pub fn lock_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response
         let refund_amount = pool_type.pool_fee;
 
         // Get the existing teams for this pool
-        // let mut teams = Vec::new();
-        let all_teams = POOL_TEAM_DETAILS.may_load(deps.storage, pool_id.clone())?;
-        match all_teams {
-            Some(some_teams) => {
-                let teams = some_teams;
-                let mut updated_teams: Vec<PoolTeamDetails> = Vec::new();
-                for team in teams {
-                    // No transfer to be done to the gamers. Just update their refund amounts.
-                    // They have to come and collect their refund
-                    let mut updated_team = team.clone();
-                    updated_team.refund_amount = refund_amount;
-                    updated_team.claimed_refund = UNCLAIMED_REFUND;
-                    println!(
-                        "refund for {:?} is {:?}",
-                        team.team_id, updated_team.refund_amount
-                    );
-                    updated_teams.push(updated_team);
-                }
-                POOL_TEAM_DETAILS.save(deps.storage, pool_id.clone(), &updated_teams)?;
-            }
-            None => {}
+        let teams = query_pool_teams(deps.storage, pool_id.clone())?;
+        for team in teams {
+            // No transfer to be done to the gamers. Just update their refund amounts.
+            // They have to come and collect their refund
+            let mut updated_team = team.clone();
+            updated_team.refund_amount = refund_amount;
+            updated_team.claimed_refund = UNCLAIMED_REFUND;
+            println!(
+                "refund for {:?} is {:?}",
+                team.team_id, updated_team.refund_amount
+            );
+            save_pool_team(deps.storage, &updated_team)?;
         }
     }
     return Ok(Response::new()
@@ -323,8 +517,9 @@ pub fn lock_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response
 
 pub fn create_pool(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
+    game_id: String,
     pool_type: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
@@ -333,7 +528,6 @@ pub fn create_pool(
             invoker: info.sender.to_string(),
         });
     }
-    let game_id = config.game_id;
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
     match gd {
@@ -346,15 +540,25 @@ pub fn create_pool(
             }));
         }
     }
-    if game.game_status != GAME_POOL_OPEN {
+    if game_status_at(&game, env.block.time) != GAME_POOL_OPEN {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("Game is not open for bidding"),
         }));
     }
 
+    let pool_id_str = save_new_pool(deps.storage, deps.api, game_id, pool_type)?;
+    return Ok(Response::new().add_attribute("pool_id", pool_id_str.clone()));
+}
+
+fn save_new_pool(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    game_id: String,
+    pool_type: String,
+) -> StdResult<String> {
     let dummy_wallet = String::from(DUMMY_WALLET);
-    let address = deps.api.addr_validate(dummy_wallet.clone().as_str())?;
-    let cpc = CONTRACT_POOL_COUNT.may_load(deps.storage, &address)?;
+    let address = api.addr_validate(dummy_wallet.clone().as_str())?;
+    let cpc = CONTRACT_POOL_COUNT.may_load(storage, &address)?;
     let global_pool_id;
     match cpc {
         Some(cpc) => {
@@ -366,7 +570,7 @@ pub fn create_pool(
     }
     let mut count = global_pool_id;
     CONTRACT_POOL_COUNT.update(
-        deps.storage,
+        storage,
         &address,
         |global_pool_id: Option<Uint128>| -> StdResult<_> {
             Ok(global_pool_id.unwrap_or_default() + Uint128::from(1u128))
@@ -375,8 +579,8 @@ pub fn create_pool(
     count += Uint128::from(1u128);
     let pool_id_str: String = count.to_string();
 
-    POOL_DETAILS.save(
-        deps.storage,
+    pool_details().save(
+        storage,
         pool_id_str.clone(),
         &PoolDetails {
             game_id: game_id.clone(),
@@ -386,7 +590,7 @@ pub fn create_pool(
             rewards_distributed: REWARDS_NOT_DISTRIBUTED,
         },
     )?;
-    return Ok(Response::new().add_attribute("pool_id", pool_id_str.clone()));
+    return Ok(pool_id_str);
 }
 
 pub fn query_platform_fees(
@@ -413,12 +617,33 @@ pub fn game_pool_bid_submit(
     pool_id: String,
     team_id: String,
     amount: Uint128,
-    testing: bool,
+    max_spread: Option<Decimal>,
+    belief_price: Option<Decimal>,
+    min_receive: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    // There is a single pending swap, checked against the balance from before it
+    if PENDING_BID_SWAP.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Another bid swap is still pending"),
+        }));
+    }
+    let max_spread = max_spread.unwrap_or(Decimal::permille(DEFAULT_MAX_SPREAD_PERMILLE));
+    if max_spread > Decimal::percent(MAX_SPREAD_LIMIT_PERCENT) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Max spread cannot be over 50%"),
+        }));
+    }
     // Calculate
     let platform_fee = config.platform_fee; //  Should be in %
-    let game_id = config.clone().game_id;
+    // the pool decides which game and pool type the team is in
+    let mut pool = query_pool_details(deps.storage, pool_id.clone())?;
+    if pool.pool_type != pool_type {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Pool type does not match the pool"),
+        }));
+    }
+    let game_id = pool.game_id.clone();
     let mut messages = Vec::new(); //  Use this to append any execute messaages in the funciton
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
@@ -432,7 +657,7 @@ pub fn game_pool_bid_submit(
             }));
         }
     }
-    if game.game_status != GAME_POOL_OPEN {
+    if game_status_at(&game, env.block.time) != GAME_POOL_OPEN {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("Game is not open for bidding"),
         }));
@@ -450,57 +675,33 @@ pub fn game_pool_bid_submit(
             }));
         }
     }
-    let required_platform_fee_ust;
-    let transaction_fee;
-    match testing {
-        true => {
-            required_platform_fee_ust = Uint128::zero();
-            transaction_fee = Uint128::zero();
-        }
-        false => {
-            let fee_details = query_platform_fees(
-                ptd.unwrap().pool_fee,
-                platform_fee,
-                config.transaction_fee,
-            )?;
-            required_platform_fee_ust = fee_details.platform_fee;
-            transaction_fee = fee_details.transaction_fee;
-        }
-    }
+    let fee_details = query_platform_fees(
+        ptd.unwrap().pool_fee,
+        platform_fee,
+        config.transaction_fee,
+    )?;
+    let required_platform_fee_ust = fee_details.platform_fee;
+    let transaction_fee = fee_details.transaction_fee;
 
-    if !testing {
-        let mut asset: Asset = Asset {
-            info: AssetInfo::NativeToken { denom: info.funds[0].denom.clone() },
-            amount: info.funds[0].amount,
-        };
-        if info.funds.clone().len() != 1 {
-            return Err(ContractError::InvalidNumberOfCoinsSent {});
-        }
-        let fund = info.funds.clone();
-
-        if fund[0].denom == "uusd" {
-            if fund[0].amount < required_platform_fee_ust.add(transaction_fee) {
-                asset = Asset {
-                    info: AssetInfo::NativeToken { denom: fund[0].denom.clone() },
-                    amount: fund[0].amount,
-                };
-                println!("Asset {}", asset);
-            }
-        } else {
-            return Err(ContractError::InsufficientFeesUst {});
-        }
-        println!("Asset {}", asset);
+    if info.funds.clone().len() != 1 {
+        return Err(ContractError::InvalidNumberOfCoinsSent {});
+    }
+    let fund = info.funds.clone();
+    if fund[0].denom != "uusd" || fund[0].amount < required_platform_fee_ust.add(transaction_fee) {
+        return Err(ContractError::InsufficientFeesUst {});
     }
 
+    let pool_fee = query_fury_equivalent_to_ust(&deps.querier, &config, pool_type_details.pool_fee)?;
 
-    let mut pool_fee: Uint128 = pool_type_details.pool_fee;
-    if !testing {
-        pool_fee = deps.querier.query_wasm_smart(
-            config.clone().astro_proxy_address,
-            &ProxyQueryMsgs::get_fury_equivalent_to_ust {
-                ust_count: pool_type_details.pool_fee,
-            },
-        )?;
+    // The swap must return at least the pool fee less the spread allowed
+    let mut min_receive_ust = pool_type_details.pool_fee - pool_type_details.pool_fee * max_spread;
+    match min_receive {
+        Some(min_receive) => {
+            if min_receive > min_receive_ust {
+                min_receive_ust = min_receive;
+            }
+        }
+        None => {}
     }
 
     // let platform_fee = pool_fee
@@ -516,19 +717,7 @@ pub fn game_pool_bid_submit(
             msg: String::from("Amount being bid does not match the pool fee and the platform fee"),
         }));
     }
-    let mut user_team_count = 0;
-    let ptd = POOL_TEAM_DETAILS.may_load(deps.storage, pool_id.clone())?;
-    match ptd {
-        Some(std) => {
-            let all_teams = std;
-            for team in all_teams {
-                if team.gamer_address == gamer {
-                    user_team_count += 1;
-                }
-            }
-        }
-        None => {}
-    }
+    let user_team_count = query_gamer_team_count_in_pool(deps.storage, pool_id.clone(), gamer.clone());
     println!("user team count = {:?}", user_team_count);
     if user_team_count >= max_teams_for_gamer {
         return Err(ContractError::Std(StdError::GenericErr {
@@ -537,23 +726,12 @@ pub fn game_pool_bid_submit(
     }
 
     let pool_id_return;
-    let mut pool_details = query_pool_details(deps.storage, pool_id.clone())?;
 
     // check if the pool can accomodate the team
-    if pool_details.current_teams_count < max_teams_for_pool {
+    if pool.current_teams_count < max_teams_for_pool {
         pool_id_return = pool_id.clone();
-        pool_details.current_teams_count += 1;
-        POOL_DETAILS.save(
-            deps.storage,
-            pool_id.clone(),
-            &PoolDetails {
-                pool_type: pool_type.clone(),
-                pool_id: pool_id.clone(),
-                game_id: pool_details.game_id.clone(),
-                current_teams_count: pool_details.current_teams_count,
-                rewards_distributed: pool_details.rewards_distributed,
-            },
-        )?;
+        pool.current_teams_count += 1;
+        pool_details().save(deps.storage, pool_id.clone(), &pool)?;
         // Now save the team details
         save_team_details(
             deps.storage,
@@ -610,8 +788,8 @@ pub fn game_pool_bid_submit(
     };
     let swap_message = AstroPortExecute::Swap {
         offer_asset: fury_asset_info,
-        belief_price: None,
-        max_spread: Option::from(Decimal::from("0.5".to_string().parse().unwrap())),
+        belief_price,
+        max_spread: Some(max_spread),
         to: Option::from(env.contract.address.to_string()),
     };
     // let tax_in_fury = fury_asset_info.deduct_tax(&deps.querier)?;
@@ -621,14 +799,26 @@ pub fn game_pool_bid_submit(
             msg: to_binary(&swap_message)?
         },
     )?;
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: config.clone().astro_proxy_address.to_string(),
-        msg: to_binary(&swap_message).unwrap(),
-        funds: vec![Coin {
-            denom: "uusd".to_string(),
-            amount: platform_fees,
-        }],
-    }));
+    // bid_swap_reply checks what the swap returned against the balance from before it
+    let ust_balance: Uint128 = deps.querier.query_balance(env.contract.address.clone(), "uusd")?.amount;
+    PENDING_BID_SWAP.save(
+        deps.storage,
+        &PendingBidSwap {
+            ust_balance_before: ust_balance.checked_sub(platform_fees).map_err(StdError::from)?,
+            min_receive: min_receive_ust,
+        },
+    )?;
+    let swap = SubMsg::reply_on_success(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.clone().astro_proxy_address.to_string(),
+            msg: to_binary(&swap_message).unwrap(),
+            funds: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: platform_fees,
+            }],
+        }),
+        BID_SWAP_REPLY_ID,
+    );
     //  Double UST Fees Removed
     // let final_amount = asset.deduct_tax(&deps.querier)?;
     // messages.push(CosmosMsg::Bank(BankMsg::Send {
@@ -639,7 +829,186 @@ pub fn game_pool_bid_submit(
     // Nothing required to transfer anything gaming fund has arrived in the gaming contract
     return Ok(Response::new()
         .add_attribute("pool_id", pool_id_return.clone())
-        .add_messages(messages));
+        .add_messages(messages)
+        .add_submessage(swap));
+}
+
+/// Fails the bid, and so rolls it back, when its swap returned less UST than
+/// the minimum receive of the bid
+pub fn bid_swap_reply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let pending = PENDING_BID_SWAP.load(deps.storage)?;
+    PENDING_BID_SWAP.remove(deps.storage);
+    let ust_balance = deps.querier.query_balance(env.contract.address, "uusd")?.amount;
+    let received = ust_balance.saturating_sub(pending.ust_balance_before);
+    if received < pending.min_receive {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: format!(
+                "Swap returned {} uusd, less than the minimum receive of {}",
+                received, pending.min_receive
+            ),
+        }));
+    }
+    return Ok(Response::new().add_attribute("swap_received", received.to_string()));
+}
+
+pub fn game_pool_type_bid_submit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    gamer: String,
+    game_id: String,
+    pool_type: String,
+    team_id: String,
+    amount: Uint128,
+    max_spread: Option<Decimal>,
+    belief_price: Option<Decimal>,
+    min_receive: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let pool_type_details = query_pool_type_details(deps.storage, pool_type.clone())?;
+
+    // The first pool of the type in the game with room for the team
+    let pools = pool_details()
+        .idx
+        .game_pool_type
+        .prefix((game_id.clone(), pool_type.clone()))
+        .range(deps.storage, None, None, Order::Ascending);
+    let mut open_pool_id = None;
+    for item in pools {
+        let (_, pool) = item?;
+        if pool.current_teams_count >= pool_type_details.max_teams_for_pool {
+            continue;
+        }
+        let user_team_count = query_gamer_team_count_in_pool(deps.storage, pool.pool_id.clone(), gamer.clone());
+        if user_team_count < pool_type_details.max_teams_for_gamer {
+            open_pool_id = Some(pool.pool_id);
+            break;
+        }
+    }
+
+    let pool_id;
+    match open_pool_id {
+        Some(open_pool_id) => {
+            pool_id = open_pool_id;
+        }
+        None => {
+            let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
+            match gd {
+                Some(_gd) => {}
+                None => {
+                    return Err(ContractError::Std(StdError::GenericErr {
+                        msg: String::from("Game status cannot be retrieved"),
+                    }));
+                }
+            }
+            pool_id = save_new_pool(deps.storage, deps.api, game_id, pool_type.clone())?;
+        }
+    }
+
+    let rsp = game_pool_bid_submit(
+        deps,
+        env,
+        info,
+        gamer,
+        pool_type,
+        pool_id.clone(),
+        team_id,
+        amount,
+        max_spread,
+        belief_price,
+        min_receive,
+    )?;
+    return Ok(rsp.set_data(to_binary(&pool_id)?));
+}
+
+pub fn import_teams(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    teams: Vec<ImportedTeam>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::Unauthorized {
+            invoker: info.sender.to_string(),
+        });
+    }
+
+    let team_count = teams.len();
+    // the pool fees of the teams back their refunds and rewards
+    let mut pool_fees = Uint128::zero();
+    for team in teams.iter() {
+        let pool = query_pool_details(deps.storage, team.pool_id.clone())?;
+        pool_fees += query_pool_type_details(deps.storage, pool.pool_type)?.pool_fee;
+    }
+    let funds_sent = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == "uusd")
+        .map(|coin| coin.amount)
+        .unwrap_or_default();
+    if funds_sent != pool_fees || info.funds.iter().any(|coin| coin.denom != "uusd") {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: format!("Pool fees of {} uusd must be sent with the teams", pool_fees),
+        }));
+    }
+
+    for team in teams {
+        deps.api.addr_validate(&team.gamer)?;
+        let mut pool = query_pool_details(deps.storage, team.pool_id.clone())?;
+        let gd = GAME_DETAILS.may_load(deps.storage, pool.game_id.clone())?;
+        let game;
+        match gd {
+            Some(gd) => {
+                game = gd;
+            }
+            None => {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Game status cannot be retrieved"),
+                }));
+            }
+        }
+        if game_status_at(&game, env.block.time) != GAME_POOL_OPEN {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Game is not open for bidding"),
+            }));
+        }
+
+        // the same limits as for a bid
+        let pool_type_details = query_pool_type_details(deps.storage, pool.pool_type.clone())?;
+        if pool.current_teams_count >= pool_type_details.max_teams_for_pool {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("pool max team limit reached "),
+            }));
+        }
+        let user_team_count = query_gamer_team_count_in_pool(deps.storage, team.pool_id.clone(), team.gamer.clone());
+        if user_team_count >= pool_type_details.max_teams_for_gamer {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("User max team limit reached "),
+            }));
+        }
+
+        pool.current_teams_count += 1;
+        pool_details().save(deps.storage, team.pool_id.clone(), &pool)?;
+        // an imported team starts out like a team that has just bid
+        save_team_details(
+            deps.storage,
+            env.clone(),
+            team.gamer.clone(),
+            team.pool_id.clone(),
+            team.team_id.clone(),
+            pool.game_id.clone(),
+            pool.pool_type.clone(),
+            Uint128::from(INITIAL_REWARD_AMOUNT),
+            UNCLAIMED_REWARD,
+            Uint128::from(INITIAL_REFUND_AMOUNT),
+            UNCLAIMED_REFUND,
+            INITIAL_TEAM_POINTS,
+            INITIAL_TEAM_RANK,
+        )?;
+    }
+    return Ok(Response::new()
+        .add_attribute("action", "import_teams")
+        .add_attribute("team_count", team_count.to_string()));
 }
 
 pub fn save_team_details(
@@ -657,17 +1026,14 @@ pub fn save_team_details(
     team_points: u64,
     team_rank: u64,
 ) -> Result<Response, ContractError> {
-    // Get the existing teams for this pool
-    let mut teams = Vec::new();
-    let all_teams = POOL_TEAM_DETAILS.may_load(storage, pool_id.clone())?;
-    match all_teams {
-        Some(some_teams) => {
-            teams = some_teams;
-        }
-        None => {}
+    let key = (pool_id.clone(), gamer.clone(), team_id.clone());
+    if pool_team_details().may_load(storage, key)?.is_some() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Team is already in the pool"),
+        }));
     }
 
-    teams.push(PoolTeamDetails {
+    save_pool_team(storage, &PoolTeamDetails {
         gamer_address: gamer,
         game_id: game_id.clone(),
         pool_type: pool_type.clone(),
@@ -679,12 +1045,19 @@ pub fn save_team_details(
         claimed_refund: claimed_refund,
         team_points: team_points,
         team_rank: team_rank,
-    });
-    POOL_TEAM_DETAILS.save(storage, pool_id.clone(), &teams)?;
+    })?;
 
     return Ok(Response::new().add_attribute("team_id", team_id.clone()));
 }
 
+fn save_pool_team(storage: &mut dyn Storage, team: &PoolTeamDetails) -> StdResult<()> {
+    pool_team_details().save(
+        storage,
+        (team.pool_id.clone(), team.gamer_address.clone(), team.team_id.clone()),
+        team,
+    )
+}
+
 pub fn claim_reward(
     deps: DepsMut,
     info: MessageInfo,
@@ -700,34 +1073,14 @@ pub fn claim_reward(
     }
 
     let mut user_reward = Uint128::zero();
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
-        .collect();
-    for pool_id in all_pools {
-        // Get the existing teams for this pool
-        let mut teams = Vec::new();
-        let all_teams = POOL_TEAM_DETAILS.may_load(deps.storage, pool_id.clone())?;
-        match all_teams {
-            Some(some_teams) => {
-                teams = some_teams;
-            }
-            None => {}
-        }
-
-        let existing_teams = teams.clone();
-        let mut updated_teams = Vec::new();
-        for team in existing_teams {
+    // Only the teams of this gamer are read and written
+    for team in query_gamer_teams(deps.storage, gamer.clone())? {
+        if team.claimed_reward == UNCLAIMED_REWARD {
+            user_reward += team.reward_amount;
             let mut updated_team = team.clone();
-            println!("Gamer {:?} gamer_address {:?} ", gamer, team.gamer_address);
-            if gamer == team.gamer_address && team.claimed_reward == UNCLAIMED_REWARD {
-                user_reward += team.reward_amount;
-                updated_team.claimed_reward = CLAIMED_REWARD;
-            }
-            updated_teams.push(updated_team);
+            updated_team.claimed_reward = CLAIMED_REWARD;
+            save_pool_team(deps.storage, &updated_team)?;
         }
-        POOL_TEAM_DETAILS.save(deps.storage, pool_id.clone(), &updated_teams)?;
     }
 
     println!("reward amount is {:?}", user_reward);
@@ -768,36 +1121,24 @@ pub fn claim_refund(
 
 
     let mut user_refund = Uint128::zero();
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
-        .collect();
-    for pool_id in all_pools {
-        // Get the existing teams for this pool
-        let mut teams = Vec::new();
-        let all_teams = POOL_TEAM_DETAILS.may_load(deps.storage, pool_id.clone())?;
-        match all_teams {
-            Some(some_teams) => {
-                teams = some_teams;
+    // Only the teams of this gamer are read and written
+    for team in query_gamer_teams(deps.storage, gamer.clone())? {
+        let mut updated_team = team.clone();
+        let unsettled_refund = query_unsettled_pool_refund(deps.storage, team.pool_id.clone(), env.block.time)?;
+        if let Some(refund_amount) = unsettled_refund {
+            if team.refund_amount == Uint128::zero() {
+                updated_team.refund_amount = refund_amount;
+                updated_team.claimed_refund = UNCLAIMED_REFUND;
             }
-            None => {}
         }
-        let existing_teams = teams.clone();
-        let mut updated_teams = Vec::new();
-        for team in existing_teams {
-            let mut updated_team = team.clone();
-            println!("Gamer {:?} gamer_address {:?} ", gamer, team.gamer_address);
-            if gamer == team.gamer_address && team.claimed_refund == UNCLAIMED_REFUND {
-                user_refund += team.refund_amount;
-                updated_team.claimed_refund = CLAIMED_REFUND;
-                let pool_details = query_pool_type_details(deps.storage, team.pool_type)?;
-                let refund_details = query_platform_fees(pool_details.pool_fee, config.platform_fee, config.transaction_fee)?;
-                refund_in_ust_fees += refund_details.transaction_fee.add(refund_details.platform_fee);
-            }
-            updated_teams.push(updated_team);
+        if updated_team.claimed_refund == UNCLAIMED_REFUND {
+            user_refund += updated_team.refund_amount;
+            updated_team.claimed_refund = CLAIMED_REFUND;
+            let pool_details = query_pool_type_details(deps.storage, team.pool_type)?;
+            let refund_details = query_platform_fees(pool_details.pool_fee, config.platform_fee, config.transaction_fee)?;
+            refund_in_ust_fees += refund_details.transaction_fee.add(refund_details.platform_fee);
+            save_pool_team(deps.storage, &updated_team)?;
         }
-        POOL_TEAM_DETAILS.save(deps.storage, pool_id.clone(), &updated_teams)?;
     }
 
     println!("refund amount is {:?}", user_refund);
@@ -822,8 +1163,9 @@ pub fn claim_refund(
 
 pub fn game_pool_reward_distribute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
+    game_id: String,
     pool_id: String,
     game_winners: Vec<GameResult>,
 ) -> Result<Response, ContractError> {
@@ -835,7 +1177,6 @@ pub fn game_pool_reward_distribute(
     }
     let platform_fee_in_percentage = config.platform_fee;
     let platform_fee;
-    let game_id = config.game_id;
 
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
@@ -849,46 +1190,40 @@ pub fn game_pool_reward_distribute(
             }));
         }
     }
-    if game.game_status == GAME_CANCELLED {
+    // The pools left are refunded once the settle deadline has passed,
+    // even those of a game with some pools already distributed
+    if let Some(settle_deadline) = game.settle_deadline {
+        if env.block.time >= settle_deadline {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Rewards cant be distributed after the settle deadline"),
+            }));
+        }
+    }
+    let game_status = game_status_at(&game, env.block.time);
+    if game_status == GAME_CANCELLED {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("Rewards cant be distributed as game is cancelled"),
         }));
     }
-    if game.game_status == GAME_POOL_OPEN {
+    if game_status == GAME_POOL_OPEN {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("Rewards cant be distributed as game not yet started"),
         }));
     }
-    GAME_DETAILS.save(
-        deps.storage,
-        game_id.clone(),
-        &GameDetails {
-            game_id: game_id.clone(),
-            game_status: GAME_COMPLETED,
-        },
-    )?;
-
-    let pool_details = query_pool_details(deps.storage, pool_id.clone())?;
-    if pool_details.rewards_distributed == REWARDS_DISTRIBUTED {
+    let pool = query_pool_details(deps.storage, pool_id.clone())?;
+    if pool.game_id != game_id {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Pool does not belong to the game"),
+        }));
+    }
+    if pool.rewards_distributed == REWARDS_DISTRIBUTED {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("Rewards are already distributed for this pool"),
         }));
     }
 
-    let pool_count = pool_details.current_teams_count;
-    let pool_type = pool_details.pool_type;
-    POOL_DETAILS.save(
-        deps.storage,
-        pool_id.clone(),
-        &PoolDetails {
-            game_id: game_id.clone(),
-            pool_id: pool_id.clone(),
-            pool_type: pool_type.clone(),
-            current_teams_count: pool_details.current_teams_count,
-            rewards_distributed: REWARDS_DISTRIBUTED,
-        },
-    )?;
-
+    let pool_count = pool.current_teams_count;
+    let pool_type = pool.pool_type;
     let pool_type_details;
     let ptd = POOL_TYPE_DETAILS.may_load(deps.storage, pool_type.clone())?;
     match ptd {
@@ -901,6 +1236,13 @@ pub fn game_pool_reward_distribute(
             }));
         }
     }
+    // A pool short of teams is refunded instead, even when the game
+    // was closed by its lock_at without LockGame being called
+    if pool_count < pool_type_details.min_teams_for_pool {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Pool does not have the minimum number of teams"),
+        }));
+    }
     platform_fee = query_platform_fees(pool_type_details.pool_fee, platform_fee_in_percentage, config.transaction_fee.clone())?.platform_fee;
 
 
@@ -917,69 +1259,104 @@ pub fn game_pool_reward_distribute(
         .checked_mul(Uint128::from(pool_count))
         .unwrap_or_default();
 
+    // The rake wallets take their percentages of the collection, a payout
+    // table shares out what is left
+    let rake_percentage: u32 = pool_type_details.rake_list.iter().map(|wp| wp.percentage).sum();
+    let prize = total_reward.multiply_ratio(100u32.saturating_sub(rake_percentage), 100u32);
+
+    // Get all teams for this pool
+    let all_teams = query_pool_teams(deps.storage, pool_id.clone())?;
+    let game_winners = validate_game_winners(
+        &all_teams,
+        &game_id,
+        game_winners,
+        &pool_type_details.payout_table,
+        prize,
+    )?;
+
     let mut winner_rewards = Uint128::zero();
     let winners = game_winners.clone();
     for winner in winners {
         winner_rewards += winner.reward_amount;
     }
-    if total_reward <= winner_rewards {
+    // rewards supplied by the admin have to leave the rake percentages
+    // and something for the collector wallet when there are none
+    if prize < winner_rewards
+        || (pool_type_details.payout_table.is_empty() && total_reward == winner_rewards)
+    {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("reward amounts do not match"),
         }));
     }
 
+    pool_details().save(
+        deps.storage,
+        pool_id.clone(),
+        &PoolDetails {
+            game_id: game_id.clone(),
+            pool_id: pool_id.clone(),
+            pool_type: pool_type.clone(),
+            current_teams_count: pool.current_teams_count,
+            rewards_distributed: REWARDS_DISTRIBUTED,
+        },
+    )?;
+
+    // The game is over once each of its pools is distributed,
+    // or refunded for being short of teams
+    let mut game_completed = true;
+    for other_pool in query_all_pools_in_game(deps.storage, game_id.clone())? {
+        if other_pool.rewards_distributed == REWARDS_DISTRIBUTED {
+            continue;
+        }
+        let other_pool_type = query_pool_type_details(deps.storage, other_pool.pool_type)?;
+        if other_pool.current_teams_count >= other_pool_type.min_teams_for_pool {
+            game_completed = false;
+            break;
+        }
+    }
+    let game_status_attribute;
+    if game_completed {
+        GAME_DETAILS.save(
+            deps.storage,
+            game_id.clone(),
+            &GameDetails {
+                game_id: game_id.clone(),
+                game_status: GAME_COMPLETED,
+                lock_at: game.lock_at,
+                settle_deadline: game.settle_deadline,
+            },
+        )?;
+        game_status_attribute = "GAME_COMPLETED";
+    } else {
+        game_status_attribute = "GAME_POOL_CLOSED";
+    }
+
     let rake_amount = total_reward - winner_rewards;
     println!(
         "total_reward {:?} winner_rewards {:?} rake_amount {:?}",
         total_reward, winner_rewards, rake_amount
     );
 
-    let mut wallet_transfer_details: Vec<WalletTransferDetails> = Vec::new();
-
     let total_platform_fee = platform_fee
         .checked_mul(Uint128::from(pool_count))
         .unwrap_or_default();
-    // Transfer total_platform_fee to platform wallets
+    // The transaction fee of each team is not credited, it pays
+    // the fee of the proxy for the swap of the bid
+    // Credit total_platform_fee to the platform wallets
     // These are the refund and development wallets
-    let all_wallet_names: Vec<String> = PLATFORM_WALLET_PERCENTAGES
-        .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
-        .collect();
-    let mut total_transfer_amount_in_fury = Uint128::zero();
-    for wallet_name in all_wallet_names {
-        let wallet = PLATFORM_WALLET_PERCENTAGES.load(deps.storage, wallet_name.clone())?;
-        let wallet_address = wallet.wallet_address;
-        let proportionate_amount = total_platform_fee
-            .checked_mul(Uint128::from(wallet.percentage))
-            .unwrap_or_default()
-            .checked_div(Uint128::from(100u128))
-            .unwrap_or_default();
-        // Transfer proportionate_amount to the corresponding platform wallet
-
-        let transfer_detail = WalletTransferDetails {
-            wallet_address: wallet_address.clone(),
-            amount: proportionate_amount,
-        };
-        total_transfer_amount_in_fury += proportionate_amount;
-        wallet_transfer_details.push(transfer_detail);
-        println!(
-            "transferring {:?} to {:?}",
-            proportionate_amount,
-            wallet_address.clone()
-        );
-    }
+    let platform_wallets: Vec<WalletPercentage> = PLATFORM_WALLET_PERCENTAGES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, wallet)| wallet))
+        .collect::<StdResult<_>>()?;
+    let platform_fee_remainder = credit_rake_wallets(
+        deps.storage,
+        total_platform_fee,
+        total_platform_fee,
+        platform_wallets,
+        config.platform_fees_collector_wallet.to_string(),
+    )?;
 
-    // Get all teams for this pool
     let mut reward_given_so_far = Uint128::zero();
-    let mut all_teams: Vec<PoolTeamDetails> = Vec::new();
-    let ptd = POOL_TEAM_DETAILS.may_load(deps.storage, pool_id.clone())?;
-    match ptd {
-        Some(ptd) => {
-            all_teams = ptd;
-        }
-        None => {}
-    }
-    let mut updated_teams: Vec<PoolTeamDetails> = Vec::new();
     for team in all_teams {
         // No transfer to be done to the winners. Just update their reward amounts.
         // They have to come and collect their rewards
@@ -998,80 +1375,182 @@ pub fn game_pool_reward_distribute(
                     "reward for {:?} is {:?}",
                     team.team_id, updated_team.reward_amount
                 );
+                save_pool_team(deps.storage, &updated_team)?;
             }
         }
-        updated_teams.push(updated_team);
-    }
-    POOL_TEAM_DETAILS.save(deps.storage, pool_id.clone(), &updated_teams)?;
-
-    // Transfer rake_amount to all the rake wallets. Can also be only one rake wallet
-    for wallet in pool_type_details.rake_list {
-        let wallet_address = wallet.wallet_address;
-        let proportionate_amount = rake_amount
-            .checked_mul(Uint128::from(wallet.percentage))
-            .unwrap_or_default()
-            .checked_div(Uint128::from(100u128))
-            .unwrap_or_default();
-        // Transfer proportionate_amount to the corresponding rake wallet
-        let transfer_detail = WalletTransferDetails {
-            wallet_address: wallet_address.clone(),
-            amount: proportionate_amount,
-        };
-        wallet_transfer_details.push(transfer_detail);
-        println!(
-            "transferring {:?} to {:?}",
-            proportionate_amount,
-            wallet_address.clone()
-        );
-    }
-
-    // let rsp = transfer_to_multiple_wallets(
-    //     wallet_transfer_details,
-    //     "rake_and_platform_fee".to_string(),
-    //     deps,
-    // )?;
+    }
+
+    // Credit rake_amount to all the rake wallets. Can also be only one rake wallet
+    let rake_remainder = credit_rake_wallets(
+        deps.storage,
+        rake_amount,
+        total_reward,
+        pool_type_details.rake_list,
+        config.platform_fees_collector_wallet.to_string(),
+    )?;
+
     return Ok(Response::new()
-        .add_attribute("game_status", "GAME_COMPLETED".to_string())
+        .add_attribute("game_status", game_status_attribute.to_string())
         .add_attribute("game_id", game_id.clone())
         .add_attribute("pool_status", "POOL_REWARD_DISTRIBUTED".to_string())
-        .add_attribute("pool_id", pool_id.clone()));
+        .add_attribute("pool_id", pool_id.clone())
+        .add_attribute("rake_amount", rake_amount.to_string())
+        .add_attribute("platform_fee_amount", total_platform_fee.to_string())
+        .add_attribute("remainder_amount", rake_remainder.add(platform_fee_remainder).to_string()));
 }
 
-pub fn _transfer_to_multiple_wallets(
-    wallet_details: Vec<WalletTransferDetails>,
-    action: String,
+/// Checks the winners against the teams of the pool. Every winner must be a team
+/// of the pool in this game, listed once and with a rank of its own. With a payout
+/// table the rewards are computed from the ranks as percentages of the prize,
+/// otherwise the rewards supplied must not increase with rank.
+fn validate_game_winners(
+    teams: &[PoolTeamDetails],
+    game_id: &str,
+    game_winners: Vec<GameResult>,
+    payout_table: &[u32],
+    prize: Uint128,
+) -> Result<Vec<GameResult>, ContractError> {
+    let mut winners = game_winners;
+    for (i, winner) in winners.iter().enumerate() {
+        let in_pool = winner.game_id == game_id
+            && teams.iter().any(|team| {
+                team.gamer_address == winner.gamer_address
+                    && team.team_id == winner.team_id
+                    && team.game_id == winner.game_id
+            });
+        if !in_pool {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Winner is not a team in this pool"),
+            }));
+        }
+        let listed_before = &winners[..i];
+        if listed_before.iter().any(|other| {
+            other.gamer_address == winner.gamer_address && other.team_id == winner.team_id
+        }) {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Winner appears more than once"),
+            }));
+        }
+        if winner.team_rank == 0 {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Winner ranks start at 1"),
+            }));
+        }
+        if listed_before.iter().any(|other| other.team_rank == winner.team_rank) {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Winner ranks must be unique"),
+            }));
+        }
+    }
+
+    if !payout_table.is_empty() {
+        for winner in winners.iter_mut() {
+            if !winner.reward_amount.is_zero() {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Rewards are computed from the payout table"),
+                }));
+            }
+            // Ranks past the end of the table get nothing
+            let percentage = payout_table
+                .get((winner.team_rank - 1) as usize)
+                .copied()
+                .unwrap_or(0);
+            winner.reward_amount = prize.multiply_ratio(percentage, 100u128);
+        }
+        return Ok(winners);
+    }
+
+    let mut by_rank: Vec<&GameResult> = winners.iter().collect();
+    by_rank.sort_by_key(|winner| winner.team_rank);
+    if by_rank
+        .windows(2)
+        .any(|pair| pair[1].reward_amount > pair[0].reward_amount)
+    {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Rewards must not increase with rank"),
+        }));
+    }
+    return Ok(winners);
+}
+
+/// Credits each wallet its percentage (out of 100) of `share_of` in the rake ledger,
+/// out of the amount. Whatever is left of the amount, unallocated percentages and
+/// rounding dust alike, is credited to the remainder wallet. Returns the amount left over.
+fn credit_rake_wallets(
+    storage: &mut dyn Storage,
+    amount: Uint128,
+    share_of: Uint128,
+    wallets: Vec<WalletPercentage>,
+    remainder_wallet: String,
+) -> StdResult<Uint128> {
+    let mut credited_so_far = Uint128::zero();
+    for wallet in wallets {
+        let proportionate_amount = share_of
+            .checked_mul(Uint128::from(wallet.percentage))?
+            .checked_div(Uint128::from(100u128))?;
+        credit_rake_ledger(storage, wallet.wallet_address, proportionate_amount)?;
+        credited_so_far += proportionate_amount;
+    }
+    let remainder = amount.checked_sub(credited_so_far)?;
+    credit_rake_ledger(storage, remainder_wallet, remainder)?;
+    return Ok(remainder);
+}
+
+fn credit_rake_ledger(
+    storage: &mut dyn Storage,
+    wallet_address: String,
+    amount: Uint128,
+) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let mut entry = RAKE_LEDGER
+        .may_load(storage, wallet_address.clone())?
+        .unwrap_or(RakeLedgerEntry {
+            wallet_address: wallet_address.clone(),
+            credited: Uint128::zero(),
+            claimed: Uint128::zero(),
+        });
+    entry.credited += amount;
+    RAKE_LEDGER.save(storage, wallet_address, &entry)
+}
+
+pub fn claim_rake(
     deps: DepsMut,
+    wallet: String,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let mut rsp = Response::new();
-    for wallet in wallet_details {
-        let current_amt = deps.querier.query_wasm_smart(
-            config.astro_proxy_address.clone(),
-            &ProxyQueryMsgs::get_ust_equivalent_to_fury {
-                fury_count: wallet.amount,
-            },
-        )?;
-        let swap_message = AstroPortExecute::Swap {
-            offer_asset: Asset {
-                info: AssetInfo::NativeToken {
-                    denom: "uusd".to_string()
-                },
-                amount: current_amt,
-            },
-            belief_price: None,
-            max_spread: Option::from(Decimal::from("0.1".to_string().parse().unwrap())),
-            to: Option::from(wallet.wallet_address.to_string()),
-        };
-        let exec = WasmMsg::Execute {
-            contract_addr: config.astro_proxy_address.to_string(),
-            msg: to_binary(&swap_message).unwrap(),
-            funds: vec![],
-        };
-        let send: SubMsg = SubMsg::new(exec);
-        rsp = rsp.add_submessage(send);
+    let rl = RAKE_LEDGER.may_load(deps.storage, wallet.clone())?;
+    let mut entry;
+    match rl {
+        Some(rl) => {
+            entry = rl;
+        }
+        None => {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("No rake for this wallet"),
+            }));
+        }
+    }
+    let unclaimed = entry.credited - entry.claimed;
+    if unclaimed.is_zero() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("No rake for this wallet"),
+        }));
     }
-    let data_msg = format!("Amount transferred").into_bytes();
-    Ok(rsp.add_attribute("action", action).set_data(data_msg))
+    entry.claimed = entry.credited;
+    RAKE_LEDGER.save(deps.storage, wallet.clone(), &entry)?;
+
+    return Ok(Response::new()
+        .add_message(CosmosMsg::Bank(BankMsg::Send {
+            to_address: wallet.clone(),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: unclaimed,
+            }],
+        }))
+        .add_attribute("action", "claim_rake")
+        .add_attribute("wallet", wallet)
+        .add_attribute("amount", unclaimed.to_string()));
 }
 
 pub fn transfer_from_contract_to_wallet(
@@ -1,6 +1,6 @@
 use astroport::asset::Asset;
 use astroport::factory::PairType;
-use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Timestamp, Uint128};
 use cw20::{Cw20ReceiveMsg, Logo};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -21,10 +21,12 @@ pub struct InstantiateMsg {
     pub transaction_fee: Uint128,
     pub astro_proxy_address: String,
     pub platform_fee: Uint128,
-    pub game_id: String,
+    /// the astro proxy when not set
+    #[serde(default)]
+    pub price_source: Option<PriceSource>,
 }
 
-use crate::state::{GameResult, WalletPercentage};
+use crate::state::{GameResult, PriceSource, WalletPercentage};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MigrateMsg {}
@@ -36,6 +38,12 @@ pub enum ExecuteMsg {
     SetPlatformFeeWallets {
         wallet_percentages: Vec<WalletPercentage>
     },
+    /// Admin only. Sets where bids look up the FURY price of the pool fee
+    SetPriceSource {
+        price_source: PriceSource,
+    },
+    /// Admin only. Sets the params of a new pool type. The wallet percentages
+    /// are the rake, each a percentage of the pool collection
     SetPoolTypeParams {
         pool_type: String,
         pool_fee: Uint128,
@@ -44,9 +52,30 @@ pub enum ExecuteMsg {
         max_teams_for_gamer: u32,
         wallet_percentages: Vec<WalletPercentage>,
     },
-    CancelGame {},
-    LockGame {},
+    /// Admin only. Sets the reward percentages (out of 100) by team rank for
+    /// the pool type. The percentages are of the prize, the pool collection less
+    /// the rake percentages of the pool type. Winners of its pools are then given
+    /// with a zero reward_amount and rewarded by rank. An empty table turns this off
+    SetPoolTypePayoutTable {
+        pool_type: String,
+        payout_table: Vec<u32>,
+    },
+    /// Admin only. Opens a new game for bidding
+    CreateGame {
+        game_id: String,
+        /// bids are taken until the game is locked when not set
+        lock_at: Option<Timestamp>,
+        /// rewards can be distributed at any time when not set
+        settle_deadline: Option<Timestamp>,
+    },
+    CancelGame {
+        game_id: String,
+    },
+    LockGame {
+        game_id: String,
+    },
     CreatePool {
+        game_id: String,
         pool_type: String
     },
     ClaimReward {
@@ -55,30 +84,53 @@ pub enum ExecuteMsg {
     ClaimRefund {
         gamer: String
     },
+    /// Pays out the rake and platform fee credited to the wallet.
+    /// Anyone can trigger it, the funds always go to the wallet
+    ClaimRake {
+        wallet: String
+    },
     GamePoolRewardDistribute {
+        game_id: String,
         pool_id: String,
         game_winners: Vec<GameResult>,
     },
-    SaveTeamDetails {
+    /// Admin only. Adds teams to pools of games still open for bidding,
+    /// within the limits of the pool type, as if they had bid.
+    /// The pool fees of all the teams have to be sent along in uusd
+    ImportTeams {
+        teams: Vec<ImportedTeam>,
+    },
+    GamePoolBidSubmitCommand {
         gamer: String,
+        pool_type: String,
         pool_id: String,
         team_id: String,
-        game_id: String,
-        pool_type: String,
-        reward_amount: Uint128,
-        claimed_reward: bool,
-        refund_amount: Uint128,
-        claimed_refund: bool,
-        team_points: u64,
-        team_rank: u64,
+        amount: Uint128,
+        /// spread allowed on the swap of the bid to UST, 0.5% when not set
+        #[serde(default)]
+        max_spread: Option<Decimal>,
+        #[serde(default)]
+        belief_price: Option<Decimal>,
+        /// least UST the swap must return, never below the pool fee less max_spread
+        #[serde(default)]
+        min_receive: Option<Uint128>,
     },
-    GamePoolBidSubmitCommand {
+    /// Like GamePoolBidSubmitCommand, with the pool picked by the contract.
+    /// See GamePoolTypeBidSubmitCommand
+    GamePoolTypeBidSubmitCommand {
         gamer: String,
+        game_id: String,
         pool_type: String,
-        pool_id: String,
         team_id: String,
         amount: Uint128,
-
+        /// spread allowed on the swap of the bid to UST, 0.5% when not set
+        #[serde(default)]
+        max_spread: Option<Decimal>,
+        #[serde(default)]
+        belief_price: Option<Decimal>,
+        /// least UST the swap must return, never below the pool fee less max_spread
+        #[serde(default)]
+        min_receive: Option<Uint128>,
     },
 }
 
@@ -97,22 +149,29 @@ pub enum QueryMsg {
     AllPoolTypeDetails {},
     AllTeams {},
     QueryReward {
-        gamer: String
+        gamer: String,
+        game_id: String,
     },
     QueryRefund {
-        gamer: String
+        gamer: String,
+        game_id: String,
     },
     QueryGameResult {
         gamer: String,
         pool_id: String,
         team_id: String,
     },
-    GameDetails {},
+    GameDetails {
+        game_id: String,
+    },
+    AllGames {},
     PoolTeamDetailsWithTeamId {
         pool_id: String,
         team_id: String,
     },
-    AllPoolsInGame {},
+    AllPoolsInGame {
+        game_id: String,
+    },
     PoolCollection {
         pool_id: String,
     },
@@ -121,6 +180,7 @@ pub enum QueryMsg {
         game_id: String,
         pool_type: String,
     },
+    RakeLedger {},
 }
 
 
@@ -128,6 +188,7 @@ pub enum QueryMsg {
 #[serde(rename_all = "snake_case")]
 pub enum ReceivedMsg {
     GamePoolBidSubmit(GamePoolBidSubmitCommand),
+    GamePoolTypeBidSubmit(GamePoolTypeBidSubmitCommand),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -136,6 +197,40 @@ pub struct GamePoolBidSubmitCommand {
     pub pool_type: String,
     pub pool_id: String,
     pub team_id: String,
+    /// spread allowed on the swap of the bid to UST, 0.5% when not set
+    #[serde(default)]
+    pub max_spread: Option<Decimal>,
+    #[serde(default)]
+    pub belief_price: Option<Decimal>,
+    /// least UST the swap must return, never below the pool fee less max_spread
+    #[serde(default)]
+    pub min_receive: Option<Uint128>,
+}
+
+/// A bid on a pool type of a game rather than on a pool. The team goes into
+/// the first pool of the type with room for it, or into a new pool when all
+/// are full. The pool id is returned in the response data
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GamePoolTypeBidSubmitCommand {
+    pub gamer: String,
+    pub game_id: String,
+    pub pool_type: String,
+    pub team_id: String,
+    /// spread allowed on the swap of the bid to UST, 0.5% when not set
+    #[serde(default)]
+    pub max_spread: Option<Decimal>,
+    #[serde(default)]
+    pub belief_price: Option<Decimal>,
+    /// least UST the swap must return, never below the pool fee less max_spread
+    #[serde(default)]
+    pub min_receive: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ImportedTeam {
+    pub gamer: String,
+    pub pool_id: String,
+    pub team_id: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
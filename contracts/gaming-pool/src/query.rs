@@ -1,6 +1,7 @@
-use cosmwasm_std::{Deps, Order, StdError, StdResult, Storage, Uint128};
-use crate::contract::{DUMMY_WALLET, INITIAL_TEAM_POINTS, INITIAL_TEAM_RANK, UNCLAIMED_REFUND, UNCLAIMED_REWARD};
-use crate::state::{CONFIG, GAME_DETAILS, GAME_RESULT_DUMMY, GameDetails, GameResult, POOL_DETAILS, POOL_TEAM_DETAILS, POOL_TYPE_DETAILS, PoolDetails, PoolTeamDetails, PoolTypeDetails};
+use cosmwasm_std::{Deps, Order, StdError, StdResult, Storage, Timestamp, Uint128};
+use crate::contract::{DUMMY_WALLET, GAME_CANCELLED, GAME_POOL_CLOSED, GAME_POOL_OPEN, INITIAL_TEAM_POINTS,
+                      INITIAL_TEAM_RANK, REWARDS_DISTRIBUTED, UNCLAIMED_REFUND, UNCLAIMED_REWARD};
+use crate::state::{GAME_DETAILS, GAME_RESULT_DUMMY, GameDetails, GameResult, pool_details, POOL_TYPE_DETAILS, PoolDetails, PoolTeamDetails, PoolTypeDetails, RAKE_LEDGER, RakeLedgerEntry, pool_team_details};
 
 pub fn query_pool_type_details(
     storage: &dyn Storage,
@@ -30,21 +31,50 @@ pub fn query_pool_team_details(
     storage: &dyn Storage,
     pool_id: String,
 ) -> StdResult<Vec<PoolTeamDetails>> {
-    let ptd = POOL_TEAM_DETAILS.may_load(storage, pool_id)?;
-    match ptd {
-        Some(ptd) => return Ok(ptd),
-        None => return Err(StdError::generic_err("No team details found")),
-    };
+    let ptd = query_pool_teams(storage, pool_id)?;
+    if ptd.is_empty() {
+        return Err(StdError::generic_err("No team details found"));
+    }
+    return Ok(ptd);
+}
+
+/// The teams of a pool, ordered by gamer and team id
+pub fn query_pool_teams(storage: &dyn Storage, pool_id: String) -> StdResult<Vec<PoolTeamDetails>> {
+    let teams = pool_team_details()
+        .sub_prefix(pool_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, team)| team))
+        .collect::<StdResult<_>>()?;
+    return Ok(teams);
+}
+
+/// The teams of a gamer in all pools, read through the gamer index
+pub fn query_gamer_teams(storage: &dyn Storage, gamer: String) -> StdResult<Vec<PoolTeamDetails>> {
+    let teams = pool_team_details()
+        .idx
+        .gamer
+        .prefix(gamer)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, team)| team))
+        .collect::<StdResult<_>>()?;
+    return Ok(teams);
+}
+
+pub fn query_gamer_team_count_in_pool(storage: &dyn Storage, pool_id: String, gamer: String) -> u32 {
+    return pool_team_details()
+        .prefix((pool_id, gamer))
+        .keys(storage, None, None, Order::Ascending)
+        .count() as u32;
 }
 
 pub fn query_all_teams(storage: &dyn Storage) -> StdResult<Vec<PoolTeamDetails>> {
     let mut all_teams = Vec::new();
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
-        .collect();
+    let all_pools: Vec<String> = pool_details()
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, pool)| pool.pool_id))
+        .collect::<StdResult<_>>()?;
     for pool_id in all_pools {
-        let team_details = POOL_TEAM_DETAILS.load(storage, pool_id.clone())?;
+        let team_details = query_pool_teams(storage, pool_id.clone())?;
         for team in team_details {
             all_teams.push(team);
         }
@@ -52,56 +82,82 @@ pub fn query_all_teams(storage: &dyn Storage) -> StdResult<Vec<PoolTeamDetails>>
     return Ok(all_teams);
 }
 
-pub fn query_reward(storage: &dyn Storage, gamer: String) -> StdResult<Uint128> {
+pub fn query_reward(storage: &dyn Storage, gamer: String, game_id: String) -> StdResult<Uint128> {
     let mut user_reward = Uint128::zero();
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
-        .collect();
-    for pool_id in all_pools {
-        // Get the existing teams for this pool
-        let mut teams = Vec::new();
-        let all_teams = POOL_TEAM_DETAILS.may_load(storage, pool_id.clone())?;
-        match all_teams {
-            Some(some_teams) => {
-                teams = some_teams;
-            }
-            None => {}
+    for team in query_gamer_teams(storage, gamer)? {
+        if team.game_id == game_id && team.claimed_reward == UNCLAIMED_REWARD {
+            user_reward += team.reward_amount;
+        }
+    }
+    return Ok(user_reward);
+}
+
+pub fn query_refund(storage: &dyn Storage, gamer: String, game_id: String, now: Timestamp) -> StdResult<Uint128> {
+    let mut user_reward = Uint128::zero();
+    for team in query_gamer_teams(storage, gamer)? {
+        if team.game_id != game_id {
+            continue;
         }
-        for team in teams {
-            if gamer == team.gamer_address && team.claimed_reward == UNCLAIMED_REWARD {
-                user_reward += team.reward_amount;
+        let unsettled_refund = query_unsettled_pool_refund(storage, team.pool_id.clone(), now)?;
+        match unsettled_refund {
+            Some(refund_amount) if team.refund_amount == Uint128::zero() => {
+                user_reward += refund_amount;
+            }
+            _ => {
+                if team.claimed_refund == UNCLAIMED_REFUND {
+                    user_reward += team.refund_amount;
+                }
             }
         }
     }
     return Ok(user_reward);
 }
 
-pub fn query_refund(storage: &dyn Storage, gamer: String) -> StdResult<Uint128> {
-    let mut user_reward = Uint128::zero();
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
-        .collect();
-    for pool_id in all_pools {
-        // Get the existing teams for this pool
-        let mut teams = Vec::new();
-        let all_teams = POOL_TEAM_DETAILS.may_load(storage, pool_id.clone())?;
-        match all_teams {
-            Some(some_teams) => {
-                teams = some_teams;
+/// The status of the game at the given time. Bidding closes at lock_at and
+/// a game not completed by its settle_deadline counts as cancelled
+pub fn game_status_at(game: &GameDetails, now: Timestamp) -> u64 {
+    if game.game_status == GAME_POOL_OPEN || game.game_status == GAME_POOL_CLOSED {
+        if let Some(settle_deadline) = game.settle_deadline {
+            if now >= settle_deadline {
+                return GAME_CANCELLED;
             }
-            None => {}
         }
-        for team in teams {
-            if gamer == team.gamer_address && team.claimed_refund == UNCLAIMED_REFUND {
-                user_reward += team.refund_amount;
+    }
+    if game.game_status == GAME_POOL_OPEN {
+        if let Some(lock_at) = game.lock_at {
+            if now >= lock_at {
+                return GAME_POOL_CLOSED;
             }
         }
     }
-    return Ok(user_reward);
+    return game.game_status;
+}
+
+/// The refund due to each team of a pool whose rewards were not distributed
+/// by the settle deadline of its game, or of a pool left with fewer teams than
+/// its pool type needs once its game is closed, whether by LockGame or by lock_at.
+/// None when the pool is not in either state
+pub fn query_unsettled_pool_refund(
+    storage: &dyn Storage,
+    pool_id: String,
+    now: Timestamp,
+) -> StdResult<Option<Uint128>> {
+    let pool = query_pool_details(storage, pool_id)?;
+    if pool.rewards_distributed == REWARDS_DISTRIBUTED {
+        return Ok(None);
+    }
+    let game = query_game_details(storage, pool.game_id, now)?;
+    let pool_type = query_pool_type_details(storage, pool.pool_type)?;
+    let past_settle_deadline = match game.settle_deadline {
+        Some(settle_deadline) => now >= settle_deadline,
+        None => false,
+    };
+    let short_of_teams = game.game_status != GAME_POOL_OPEN
+        && pool.current_teams_count < pool_type.min_teams_for_pool;
+    if !past_settle_deadline && !short_of_teams {
+        return Ok(None);
+    }
+    return Ok(Some(pool_type.pool_fee));
 }
 
 pub fn query_game_result(
@@ -110,8 +166,7 @@ pub fn query_game_result(
     pool_id: String,
     team_id: String,
 ) -> StdResult<GameResult> {
-    let config = CONFIG.load(deps.storage)?;
-    let game_id = config.game_id;
+    let game_id = query_pool_details(deps.storage, pool_id.clone())?.game_id;
 
     let mut reward_amount = Uint128::zero();
     let mut refund_amount = Uint128::zero();
@@ -129,21 +184,12 @@ pub fn query_game_result(
         None => return Err(StdError::generic_err("No game result details found")),
     }
 
-    // Get the existing teams for this pool
-    let mut teams = Vec::new();
-    let all_teams = POOL_TEAM_DETAILS.may_load(deps.storage, pool_id.clone())?;
-    match all_teams {
-        Some(some_teams) => {
-            teams = some_teams;
-        }
-        None => {}
-    }
-    for team in teams {
-        if gamer == team.gamer_address
-            && team_id == team.team_id
-            && game_id == team.game_id
-            && pool_id == team.pool_id
-        {
+    let ptd = pool_team_details().may_load(
+        deps.storage,
+        (pool_id.clone(), gamer.clone(), team_id.clone()),
+    )?;
+    if let Some(team) = ptd {
+        if game_id == team.game_id {
             team_rank = team.team_rank;
             team_points = team.team_points;
             if team.claimed_reward == UNCLAIMED_REWARD {
@@ -165,7 +211,7 @@ pub fn query_game_result(
 }
 
 pub fn query_pool_details(storage: &dyn Storage, pool_id: String) -> StdResult<PoolDetails> {
-    let pd = POOL_DETAILS.may_load(storage, pool_id.clone())?;
+    let pd = pool_details().may_load(storage, pool_id.clone())?;
     match pd {
         Some(pd) => return Ok(pd),
         None => return Err(StdError::generic_err("No pool details found")),
@@ -179,30 +225,22 @@ pub fn get_team_count_for_user_in_pool_type(
     pool_type: String,
 ) -> StdResult<u32> {
     let mut count = 0;
-    let all_pools: Vec<String> = POOL_TEAM_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
-        .collect();
-    for pool_id in all_pools {
-        let team_details = POOL_TEAM_DETAILS.load(storage, pool_id.clone())?;
-        for team in team_details {
-            if team.pool_type == pool_type && team.game_id == game_id && team.gamer_address == gamer && team.pool_id == pool_id
-            {
-                count += 1;
-            }
+    for team in query_gamer_teams(storage, gamer)? {
+        if team.pool_type == pool_type && team.game_id == game_id {
+            count += 1;
         }
     }
     println!("Team count for user in given pool type : {:?}", count);
     return Ok(count);
 }
 
-pub fn query_game_details(storage: &dyn Storage) -> StdResult<GameDetails> {
-    let config = CONFIG.load(storage)?;
-    let game_id = config.game_id;
-
+pub fn query_game_details(storage: &dyn Storage, game_id: String, now: Timestamp) -> StdResult<GameDetails> {
     let game_detail = GAME_DETAILS.may_load(storage, game_id)?;
     match game_detail {
-        Some(game_detail) => return Ok(game_detail),
+        Some(mut game_detail) => {
+            game_detail.game_status = game_status_at(&game_detail, now);
+            return Ok(game_detail);
+        }
         None => return Err(StdError::generic_err("No Game detail found")),
     };
 }
@@ -212,7 +250,7 @@ pub fn query_team_details(
     pool_id: String,
     team_id: String,
 ) -> StdResult<PoolTeamDetails> {
-    let team_details = POOL_TEAM_DETAILS.load(storage, pool_id.clone())?;
+    let team_details = query_pool_teams(storage, pool_id.clone())?;
     for team in team_details {
         if team.team_id == team_id.to_string() {
             return Ok(team.clone());
@@ -221,26 +259,40 @@ pub fn query_team_details(
     return Err(StdError::generic_err("Pool Team Details not found"));
 }
 
-pub fn query_all_pools_in_game(storage: &dyn Storage) -> StdResult<Vec<PoolDetails>> {
-    let config = CONFIG.load(storage)?;
-    let game_id = config.game_id;
+pub fn query_all_games(storage: &dyn Storage, now: Timestamp) -> StdResult<Vec<GameDetails>> {
+    let all_games = GAME_DETAILS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(_, mut game)| {
+                game.game_status = game_status_at(&game, now);
+                game
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    return Ok(all_games);
+}
+
+pub fn query_rake_ledger(storage: &dyn Storage) -> StdResult<Vec<RakeLedgerEntry>> {
+    let all_entries = RAKE_LEDGER
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect::<StdResult<_>>()?;
+    return Ok(all_entries);
+}
 
-    let mut all_pool_details = Vec::new();
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| String::from_utf8(k).unwrap())
-        .collect();
-    for pool_name in all_pools {
-        let pool_details = POOL_DETAILS.load(storage, pool_name)?;
-        if pool_details.game_id == game_id {
-            all_pool_details.push(pool_details);
-        }
-    }
+pub fn query_all_pools_in_game(storage: &dyn Storage, game_id: String) -> StdResult<Vec<PoolDetails>> {
+    let all_pool_details = pool_details()
+        .idx
+        .game
+        .prefix(game_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, pool)| pool))
+        .collect::<StdResult<_>>()?;
     return Ok(all_pool_details);
 }
 
 pub fn query_pool_collection(storage: &dyn Storage, pool_id: String) -> StdResult<Uint128> {
-    let pd = POOL_DETAILS.may_load(storage, pool_id.clone())?;
+    let pd = pool_details().may_load(storage, pool_id.clone())?;
     let pool;
     match pd {
         Some(pd) => pool = pd,
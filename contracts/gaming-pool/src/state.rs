@@ -1,8 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 use cw20::AllowanceResponse;
 
@@ -14,12 +14,46 @@ pub struct Config {
     pub astro_proxy_address: Addr,
     pub platform_fee: Uint128,
     pub transaction_fee: Uint128,
-    pub game_id: String,
+    #[serde(default)]
+    pub price_source: PriceSource,
+}
+
+/// Where the FURY equivalent of the UST pool fee is looked up for bids
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    /// get_fury_equivalent_to_ust on the astro proxy
+    AstroProxy {},
+    /// A set price, for test networks without a pool
+    Fixed { fury_per_ust: Decimal },
+    /// Consult on an astroport oracle, the average price over its period
+    Twap { oracle_address: Addr },
+}
+
+impl Default for PriceSource {
+    fn default() -> Self {
+        PriceSource::AstroProxy {}
+    }
 }
 
 pub const CONFIG_KEY: &str = "config";
 pub const CONFIG: Item<Config> = Item::new(CONFIG_KEY);
 
+/// Config of the versions that ran a single game, the one in game_id.
+/// Only read when migrating
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub admin_address: Addr,
+    pub minting_contract_address: Addr,
+    pub platform_fees_collector_wallet: Addr,
+    pub astro_proxy_address: Addr,
+    pub platform_fee: Uint128,
+    pub transaction_fee: Uint128,
+    pub game_id: String,
+}
+
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new(CONFIG_KEY);
+
 /// This is used for saving various vesting details
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 #[serde(rename_all = "snake_case")]
@@ -27,8 +61,17 @@ pub struct GameDetails {
     /// The game id
     pub game_id: String,
 
-    /// Current status of the game - open, close, canceled
+    /// Status of the game as last set - open, close, canceled.
+    /// The queries give the status at the current block time
     pub game_status: u64,
+
+    /// No more bids are taken after this time. Open until locked when not set
+    #[serde(default)]
+    pub lock_at: Option<Timestamp>,
+
+    /// Pools without rewards distributed by this time are refunded in full
+    #[serde(default)]
+    pub settle_deadline: Option<Timestamp>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
@@ -72,6 +115,11 @@ pub struct PoolTypeDetails {
 
     /// Rake distribution 
     pub rake_list: Vec<WalletPercentage>,
+
+    /// Reward percentages (out of 100) of the pool collection after the rake
+    /// by team rank, first entry for rank 1. Rewards are supplied by the admin when empty
+    #[serde(default)]
+    pub payout_table: Vec<u32>,
 }
 
 /// This is used for saving various vesting details
@@ -154,6 +202,16 @@ pub struct WalletTransferDetails {
     pub amount: Uint128,
 }
 
+/// Rake and platform fee owed to a wallet, in uusd. Credited when pool
+/// rewards are distributed and paid out on ClaimRake
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct RakeLedgerEntry {
+    pub wallet_address: String,
+    pub credited: Uint128,
+    pub claimed: Uint128,
+}
+
 pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowance");
 
 /// Map of games. The key is game id and the
@@ -166,19 +224,88 @@ pub const GAME_DETAILS: Map<String, GameDetails> =
 pub const POOL_TYPE_DETAILS: Map<String, PoolTypeDetails> =
     Map::new("pool_type_details");
 
+pub struct PoolIndexes<'a> {
+    // Second arg is for storing pk
+    pub game: MultiIndex<'a, (String, Vec<u8>), PoolDetails>,
+    pub game_pool_type: MultiIndex<'a, (String, String, Vec<u8>), PoolDetails>,
+}
+
+impl<'a> IndexList<PoolDetails> for PoolIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PoolDetails>> + '_> {
+        let v: Vec<&dyn Index<PoolDetails>> = vec![&self.game, &self.game_pool_type];
+        Box::new(v.into_iter())
+    }
+}
+
 /// Map of pools. The key is pool id and the
-/// PoolDetails will contain information about the pool 
-pub const POOL_DETAILS: Map<String, PoolDetails> =
-    Map::new("pool_details");
+/// PoolDetails will contain information about the pool.
+/// The game index gives the pools of a game without walking every pool,
+/// the game_pool_type index those of one pool type in a game
+pub fn pool_details<'a>() -> IndexedMap<'a, String, PoolDetails, PoolIndexes<'a>> {
+    let indexes = PoolIndexes {
+        game: MultiIndex::new(
+            |d: &PoolDetails, k| (d.game_id.clone(), k),
+            "pool_details",
+            "pool_details__game",
+        ),
+        game_pool_type: MultiIndex::new(
+            |d: &PoolDetails, k| (d.game_id.clone(), d.pool_type.clone(), k),
+            "pool_details",
+            "pool_details__game_pool_type",
+        ),
+    };
+    IndexedMap::new("pool_details", indexes)
+}
 
-/// Map of pools and its gamers. the key is pool id and the
-/// PoolBettingDetails will contain information about the betters and amount betted
-pub const POOL_TEAM_DETAILS: Map<String, Vec<PoolTeamDetails>> =
+/// The teams of each pool in a single list per pool, as stored before
+/// pool_team_details(). Only read by the migration
+pub const LEGACY_POOL_TEAM_DETAILS: Map<String, Vec<PoolTeamDetails>> =
     Map::new("pool_team_details");
 
+pub struct PoolTeamIndexes<'a> {
+    // Second arg is for storing pk
+    pub gamer: MultiIndex<'a, (String, Vec<u8>), PoolTeamDetails>,
+}
+
+impl<'a> IndexList<PoolTeamDetails> for PoolTeamIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PoolTeamDetails>> + '_> {
+        let v: Vec<&dyn Index<PoolTeamDetails>> = vec![&self.gamer];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Teams in the pools. The key is (pool id, gamer address, team id) and
+/// the gamer index gives all the teams of a gamer without walking every pool
+pub fn pool_team_details<'a>(
+) -> IndexedMap<'a, (String, String, String), PoolTeamDetails, PoolTeamIndexes<'a>> {
+    let indexes = PoolTeamIndexes {
+        gamer: MultiIndex::new(
+            |d: &PoolTeamDetails, k| (d.gamer_address.clone(), k),
+            "pool_teams",
+            "pool_teams__gamer",
+        ),
+    };
+    IndexedMap::new("pool_teams", indexes)
+}
+
 pub const CONTRACT_POOL_COUNT: Map<&Addr, Uint128> = Map::new("contract_pool_count");
 
 pub const GAME_RESULT_DUMMY: Map<&Addr, GameResult> = Map::new("game_result");
 
 pub const PLATFORM_WALLET_PERCENTAGES: Map<String, WalletPercentage> = Map::new("platform_wallet_percentages");
 
+/// Map of wallets owed rake or platform fee. The key is the wallet address
+pub const RAKE_LEDGER: Map<String, RakeLedgerEntry> = Map::new("rake_ledger");
+
+/// The FURY to UST swap of a bid, checked once the swap has replied.
+/// A bid is rejected while another one is pending
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct PendingBidSwap {
+    /// uusd held by the contract before the swap, less the fee sent with it
+    pub ust_balance_before: Uint128,
+    pub min_receive: Uint128,
+}
+
+pub const PENDING_BID_SWAP: Item<PendingBidSwap> = Item::new("pending_bid_swap");
+
@@ -2,29 +2,36 @@
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{coin, Uint128};
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{Addr};
-    use crate::contract::{CLAIMED_REWARD, GAME_CANCELLED, GAME_COMPLETED, GAME_POOL_OPEN, INITIAL_REFUND_AMOUNT, instantiate};
-    use crate::execute::{cancel_game, claim_refund, claim_reward, create_pool, game_pool_bid_submit, game_pool_reward_distribute, lock_game, save_team_details, set_platform_fee_wallets, set_pool_type_params};
-
-    use crate::msg::{InstantiateMsg};
-    use crate::query::{get_team_count_for_user_in_pool_type, query_game_details, query_pool_details, query_team_details};
-    use crate::state::{GameResult, PLATFORM_WALLET_PERCENTAGES, POOL_TEAM_DETAILS, WalletPercentage};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockStorage};
+    use cosmwasm_std::{from_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order, Pair, QuerierWrapper, Response, StdError, StdResult, Storage, to_binary};
+    use crate::ContractError;
+    use crate::contract::{CLAIMED_REFUND, CLAIMED_REWARD, INITIAL_TEAM_RANK, UNCLAIMED_REFUND, UNCLAIMED_REWARD, GAME_CANCELLED, GAME_COMPLETED, GAME_POOL_CLOSED, GAME_POOL_OPEN, INITIAL_REFUND_AMOUNT, REWARDS_NOT_DISTRIBUTED, execute, instantiate, migrate, query, reply};
+    use crate::execute::{cancel_game, claim_refund, claim_reward, create_game, create_pool, game_pool_bid_submit, game_pool_reward_distribute, game_pool_type_bid_submit, lock_game, save_team_details, set_platform_fee_wallets, set_pool_type_params, set_pool_type_payout_table};
+
+    use crate::msg::{ExecuteMsg, ImportedTeam, InstantiateMsg, MigrateMsg, QueryMsg};
+    use astroport::asset::{Asset, AssetInfo};
+    use cw_storage_plus::{Item, Map};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+    use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+    use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+    use serde::{Deserialize, Serialize};
+    use crate::query::{get_team_count_for_user_in_pool_type, query_reward, query_all_games, query_all_pools_in_game, query_all_teams, query_game_details, query_gamer_teams, query_pool_details, query_pool_teams, query_pool_type_details, query_refund, query_rake_ledger, query_team_details};
+    use crate::state::{CONFIG, GameResult, pool_details, PENDING_BID_SWAP, PendingBidSwap, LEGACY_CONFIG, LegacyConfig, LEGACY_POOL_TEAM_DETAILS, PLATFORM_WALLET_PERCENTAGES, PoolDetails, PoolTeamDetails, PriceSource, RakeLedgerEntry, WalletPercentage};
 
     #[test]
     fn test_create_and_query_game() {
         let mut deps = mock_dependencies(&[]);
         let platform_fee = Uint128::from(300000u128);
         let transaction_fee = Uint128::from(100000u128);
-        let _owner1_info = mock_info("Owner001", &[coin(1000, "stake")]);
+        let _owner1_info = mock_info("Owner001", &[coin(10000000, "uusd")]);
         let instantiate_msg = InstantiateMsg {
             minting_contract_address: "cwtoken11111".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
             transaction_fee: transaction_fee,
-            game_id: "Game001".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -33,8 +40,17 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
-        let queryRes = query_game_details(&mut deps.storage);
+        let queryRes = query_game_details(&mut deps.storage, "Game001".to_string(), mock_env().block.time);
         match queryRes {
             Ok(gameDetail) => {
                 assert_eq!(gameDetail.game_id, "Game001".to_string());
@@ -50,7 +66,7 @@ mod tests {
     #[test]
     fn test_create_and_query_pool_detail() {
         let mut deps = mock_dependencies(&[]);
-        let owner1_info = mock_info("Owner001", &[coin(1000, "stake")]);
+        let owner1_info = mock_info("Owner001", &[coin(10000000, "uusd")]);
         let platform_fee = Uint128::from(300000u128);
         let transaction_fee = Uint128::from(100000u128);
 
@@ -58,10 +74,10 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             transaction_fee: transaction_fee,
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -70,11 +86,21 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let rsp = create_pool(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToOne".to_string(),
         );
         let mut poolId = String::new();
@@ -105,7 +131,7 @@ mod tests {
     #[test]
     fn test_save_and_query_team_detail() {
         let mut deps = mock_dependencies(&[]);
-        let owner1_info = mock_info("Owner001", &[coin(1000, "stake")]);
+        let owner1_info = mock_info("Owner001", &[coin(10000000, "uusd")]);
         let platform_fee = Uint128::from(300000u128);
         let transaction_fee = Uint128::from(100000u128);
         let instantiate_msg = InstantiateMsg {
@@ -114,9 +140,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -125,11 +151,21 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let rsp = create_pool(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToOne".to_string(),
         );
         let mut poolId = String::new();
@@ -188,7 +224,7 @@ mod tests {
     #[test]
     fn test_get_team_count_for_user_in_pool_type() {
         let mut deps = mock_dependencies(&[]);
-        let owner1_info = mock_info("Owner001", &[coin(1000, "stake")]);
+        let owner1_info = mock_info("Owner001", &[coin(10000000, "uusd")]);
         let platform_fee = Uint128::from(300000u128);
         let transaction_fee = Uint128::from(100000u128);
         let instantiate_msg = InstantiateMsg {
@@ -197,9 +233,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -208,11 +244,21 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let rsp = create_pool(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToOne".to_string(),
         );
         let mut poolId = String::new();
@@ -304,9 +350,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -315,6 +361,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
 
@@ -354,6 +409,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToOne".to_string(),
         );
         let mut poolId = String::new();
@@ -378,7 +434,9 @@ mod tests {
             poolId.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         let queryRes = query_pool_details(&mut deps.storage, "1".to_string());
         match queryRes {
@@ -405,9 +463,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -416,6 +474,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
 
@@ -455,6 +522,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToOne".to_string(),
         );
         let mut poolId = String::new();
@@ -479,7 +547,9 @@ mod tests {
             poolId.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -490,7 +560,9 @@ mod tests {
             poolId.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         let queryRes = query_pool_details(&mut deps.storage, "2".to_string());
         match queryRes {
@@ -518,9 +590,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -529,6 +601,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
 
@@ -581,6 +662,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToOne".to_string(),
         );
         match rsp_1 {
@@ -598,6 +680,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "multiple".to_string(),
         );
         match rsp_2 {
@@ -616,6 +699,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToOne".to_string(),
         );
         match rsp_3 {
@@ -630,7 +714,7 @@ mod tests {
 
         let rewardInfo = mock_info("rewardInfo", &[]);
         // Adding multile team to pool_1 for Game001
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -641,7 +725,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -652,7 +738,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -663,7 +751,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -674,7 +764,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -698,7 +790,9 @@ mod tests {
             pool_id_2.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -709,7 +803,9 @@ mod tests {
             pool_id_2.to_string(),
             "Team004".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -720,7 +816,9 @@ mod tests {
             pool_id_2.to_string(),
             "Team005".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_2 = query_pool_details(&mut deps.storage, pool_id_2.to_string());
@@ -744,18 +842,22 @@ mod tests {
             pool_id_3.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
             mock_env(),
             ownerXInfo.clone(),
             "Gamer001".to_string(),
-            "multiple".to_string(),
+            "oneToOne".to_string(),
             pool_id_3.to_string(),
             "Team004".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         let query_pool_details_3 = query_pool_details(&mut deps.storage, pool_id_3.to_string());
         match query_pool_details_3 {
@@ -782,9 +884,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -793,6 +895,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -833,6 +944,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -845,7 +957,7 @@ mod tests {
             }
         }
         let rewardInfo = mock_info("rewardInfo", &[]);
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -856,7 +968,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -867,7 +981,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -878,7 +994,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -907,9 +1025,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -918,6 +1036,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -958,6 +1085,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -970,7 +1098,7 @@ mod tests {
             }
         }
         let rewardInfo = mock_info("rewardInfo", &[]);
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -981,7 +1109,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -992,7 +1122,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1003,7 +1135,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -1022,7 +1156,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team001".to_string(),
-            team_rank: 1u64,
+            team_rank: 3u64,
             team_points: 100u64,
             reward_amount: Uint128::from(100u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -1040,7 +1174,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team003".to_string(),
-            team_rank: 2u64,
+            team_rank: 1u64,
             team_points: 300u64,
             reward_amount: Uint128::from(300u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -1050,7 +1184,7 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -1070,6 +1204,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
         );
@@ -1082,7 +1217,7 @@ mod tests {
             }
         }
 
-        let query_game_status_res = query_game_details(&mut deps.storage);
+        let query_game_status_res = query_game_details(&mut deps.storage, "Game001".to_string(), mock_env().block.time);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1092,7 +1227,7 @@ mod tests {
                 assert_eq!(1, 2);
             }
         }
-        let team_details = POOL_TEAM_DETAILS.load(&mut deps.storage, pool_id_1.clone());
+        let team_details = query_pool_teams(&deps.storage, pool_id_1.clone());
         for team in team_details {
             assert_eq!(team[0].reward_amount, Uint128::from(100u128));
             assert_eq!(team[1].reward_amount, Uint128::from(200u128));
@@ -1112,9 +1247,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -1123,6 +1258,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -1163,6 +1307,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -1175,7 +1320,7 @@ mod tests {
             }
         }
         let rewardInfo = mock_info("rewardInfo", &[]);
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1186,11 +1331,13 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let cancelInfo = mock_info("cancelInfo", &[]);
-        let cancel_rsp = cancel_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let cancel_rsp = cancel_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
 
 /*
 		23 Mar 2022, commenting this out because call to proxy cannot be made 
@@ -1225,9 +1372,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -1236,6 +1383,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -1276,6 +1432,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -1288,7 +1445,7 @@ mod tests {
             }
         }
         let rewardInfo = mock_info("rewardInfo", &[]);
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1299,7 +1456,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1310,7 +1469,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1321,7 +1482,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -1340,7 +1503,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team001".to_string(),
-            team_rank: 1u64,
+            team_rank: 3u64,
             team_points: 100u64,
             reward_amount: Uint128::from(100u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -1358,7 +1521,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team003".to_string(),
-            team_rank: 2u64,
+            team_rank: 1u64,
             team_points: 300u64,
             reward_amount: Uint128::from(300u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -1368,7 +1531,7 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -1386,7 +1549,7 @@ mod tests {
 
         let cancelInfo = mock_info("cancelInfo", &[]);
         let game_pool_reward_distribute_rsp =
-            cancel_game(deps.as_mut(), mock_env(), adminInfo.clone());
+            cancel_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
 
         match game_pool_reward_distribute_rsp {
             Ok(game_pool_reward_distribute_rsp) => {}
@@ -1396,7 +1559,7 @@ mod tests {
             }
         }
 
-        let query_game_status_res = query_game_details(&mut deps.storage);
+        let query_game_status_res = query_game_details(&mut deps.storage, "Game001".to_string(), mock_env().block.time);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_CANCELLED);
@@ -1406,7 +1569,7 @@ mod tests {
                 assert_eq!(5, 6);
             }
         }
-        let team_details = POOL_TEAM_DETAILS.load(&mut deps.storage, pool_id_1.clone());
+        let team_details = query_pool_teams(&deps.storage, pool_id_1.clone());
         for team in team_details {
             assert_eq!(team[0].reward_amount, Uint128::zero());
             assert_eq!(team[1].reward_amount, Uint128::zero());
@@ -1427,9 +1590,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -1438,6 +1601,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -1478,6 +1650,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -1497,9 +1670,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let rewardInfo = mock_info("rewardInfo", &[]);
         instantiate(
@@ -1508,7 +1681,7 @@ mod tests {
             rewardInfo.clone(),
             instantiate_msg,
         );
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1519,7 +1692,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1530,7 +1705,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1541,7 +1718,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -1569,7 +1748,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team002".to_string(),
-            team_rank: 2u64,
+            team_rank: 3u64,
             team_points: 200u64,
             reward_amount: Uint128::from(200u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -1588,7 +1767,7 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -1608,6 +1787,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
         );
@@ -1620,7 +1800,7 @@ mod tests {
             }
         }
 
-        let mut query_game_status_res = query_game_details(&mut deps.storage);
+        let mut query_game_status_res = query_game_details(&mut deps.storage, "Game001".to_string(), mock_env().block.time);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1630,7 +1810,7 @@ mod tests {
                 assert_eq!(5, 6);
             }
         }
-        let team_details = POOL_TEAM_DETAILS.load(&mut deps.storage, pool_id_1.clone());
+        let team_details = query_pool_teams(&deps.storage, pool_id_1.clone());
         for team in team_details {
             assert_eq!(team[0].reward_amount, Uint128::from(500u128));
             assert_eq!(team[1].reward_amount, Uint128::from(200u128));
@@ -1659,7 +1839,7 @@ mod tests {
         }
 */
 
-        query_game_status_res = query_game_details(&mut deps.storage);
+        query_game_status_res = query_game_details(&mut deps.storage, "Game001".to_string(), mock_env().block.time);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1670,7 +1850,7 @@ mod tests {
             }
         }
 
-        let team_details = POOL_TEAM_DETAILS.load(&mut deps.storage, pool_id_1.clone());
+        let team_details = query_pool_teams(&deps.storage, pool_id_1.clone());
         for team in team_details {
             assert_eq!(team[0].reward_amount, Uint128::from(500u128)); // TODO This reward should be 0 after full functionality working.
             assert_eq!(team[1].reward_amount, Uint128::from(200u128)); // TODO This reward should be 0 after full functionality working.
@@ -1697,9 +1877,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -1708,6 +1888,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -1748,6 +1937,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -1760,7 +1950,7 @@ mod tests {
             }
         }
         let rewardInfo = mock_info("rewardInfo", &[]);
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1771,7 +1961,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1782,7 +1974,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1793,7 +1987,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -1812,7 +2008,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team001".to_string(),
-            team_rank: 1u64,
+            team_rank: 3u64,
             team_points: 100u64,
             reward_amount: Uint128::from(100u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -1830,7 +2026,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team003".to_string(),
-            team_rank: 2u64,
+            team_rank: 1u64,
             team_points: 300u64,
             reward_amount: Uint128::from(300u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -1840,7 +2036,7 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -1860,6 +2056,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
         );
@@ -1872,7 +2069,7 @@ mod tests {
             }
         }
 
-        let mut query_game_status_res = query_game_details(&mut deps.storage);
+        let mut query_game_status_res = query_game_details(&mut deps.storage, "Game001".to_string(), mock_env().block.time);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1882,7 +2079,7 @@ mod tests {
                 assert_eq!(5, 6);
             }
         }
-        let team_details = POOL_TEAM_DETAILS.load(&mut deps.storage, pool_id_1.clone());
+        let team_details = query_pool_teams(&deps.storage, pool_id_1.clone());
         for team in team_details {
             assert_eq!(team[0].reward_amount, Uint128::from(100u128));
             assert_eq!(team[1].reward_amount, Uint128::from(200u128));
@@ -1910,7 +2107,7 @@ mod tests {
             }
         }
 */
-        query_game_status_res = query_game_details(&mut deps.storage);
+        query_game_status_res = query_game_details(&mut deps.storage, "Game001".to_string(), mock_env().block.time);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1921,7 +2118,7 @@ mod tests {
             }
         }
 
-        let team_details = POOL_TEAM_DETAILS.load(&mut deps.storage, pool_id_1.clone());
+        let team_details = query_pool_teams(&deps.storage, pool_id_1.clone());
         for team in team_details {
             assert_eq!(team[0].reward_amount, Uint128::from(100u128)); // TODO This reward should be 0 after full functionality working.
             assert_eq!(team[1].reward_amount, Uint128::from(200u128)); // TODO This reward should be 0 after full functionality working.
@@ -1968,9 +2165,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -1979,6 +2176,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -2019,6 +2225,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -2038,12 +2245,12 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let rewardInfo = mock_info("rewardInfo", &[]);
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2054,7 +2261,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2065,7 +2274,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2076,7 +2287,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -2095,7 +2308,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team001".to_string(),
-            team_rank: 1u64,
+            team_rank: 3u64,
             team_points: 100u64,
             reward_amount: Uint128::from(100u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -2113,7 +2326,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team003".to_string(),
-            team_rank: 2u64,
+            team_rank: 1u64,
             team_points: 300u64,
             reward_amount: Uint128::from(300u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -2123,7 +2336,7 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -2138,7 +2351,7 @@ mod tests {
                 assert_eq!(3, 4);
             }
         }
-        let team_details = POOL_TEAM_DETAILS.load(&mut deps.storage, pool_id_1.clone());
+        let team_details = query_pool_teams(&deps.storage, pool_id_1.clone());
 		let mut teams = Vec::new();
         match team_details {
             Ok(some_teams) => {
@@ -2167,9 +2380,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -2178,6 +2391,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -2218,6 +2440,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -2231,7 +2454,7 @@ mod tests {
         }
         let rewardInfo = mock_info("rewardInfo", &[]);
 
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2242,7 +2465,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2253,7 +2478,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2264,7 +2491,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -2283,7 +2512,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team001".to_string(),
-            team_rank: 1u64,
+            team_rank: 3u64,
             team_points: 100u64,
             reward_amount: Uint128::from(100u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -2301,7 +2530,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team003".to_string(),
-            team_rank: 2u64,
+            team_rank: 1u64,
             team_points: 300u64,
             reward_amount: Uint128::from(300u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -2311,7 +2540,7 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -2331,6 +2560,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
         );
@@ -2343,7 +2573,7 @@ mod tests {
             }
         }
 
-        let mut query_game_status_res = query_game_details(&mut deps.storage);
+        let mut query_game_status_res = query_game_details(&mut deps.storage, "Game001".to_string(), mock_env().block.time);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -2354,7 +2584,7 @@ mod tests {
             }
         }
 
-        let game_cancel_rsp = cancel_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let game_cancel_rsp = cancel_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
 
         match game_cancel_rsp {
             Ok(game_cancel_rsp) => {
@@ -2383,9 +2613,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -2394,6 +2624,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -2434,6 +2673,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -2450,7 +2690,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team001".to_string(),
-            team_rank: 1u64,
+            team_rank: 3u64,
             team_points: 100u64,
             reward_amount: Uint128::from(100u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -2468,7 +2708,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team003".to_string(),
-            team_rank: 2u64,
+            team_rank: 1u64,
             team_points: 300u64,
             reward_amount: Uint128::from(300u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -2482,6 +2722,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results.clone(),
         );
@@ -2501,7 +2742,7 @@ mod tests {
         }
 
         let rewardInfo = mock_info("rewardInfo", &[]);
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2512,7 +2753,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2523,7 +2766,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2534,7 +2779,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -2553,6 +2800,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
         );
@@ -2585,9 +2833,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let adminInfo = mock_info("admin11111", &[]);
         instantiate(
@@ -2596,6 +2844,15 @@ mod tests {
             adminInfo.clone(),
             instantiate_msg,
         );
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         let mut rake_list: Vec<WalletPercentage> = Vec::new();
         let rake_1 = WalletPercentage {
@@ -2636,6 +2893,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             "oneToTwo".to_string(),
         );
         match rsp_1 {
@@ -2655,12 +2913,12 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
         let rewardInfo = mock_info("rewardInfo", &[]);
-        let ownerXInfo = mock_info("cwtoken11111", &[coin(1000, "stake")]);
+        let ownerXInfo = mock_info("cwtoken11111", &[coin(10000000, "uusd")]);
         // Adding same team twice in same pool
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2671,7 +2929,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team001".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2682,7 +2942,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team002".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2693,7 +2955,9 @@ mod tests {
             pool_id_1.to_string(),
             "Team003".to_string(),
             Uint128::from(144262u128) + platform_fee,
-            true,
+            None,
+            None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -2712,7 +2976,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team001".to_string(),
-            team_rank: 1u64,
+            team_rank: 3u64,
             team_points: 100u64,
             reward_amount: Uint128::from(100u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -2730,7 +2994,7 @@ mod tests {
             gamer_address: "Gamer002".to_string(),
             game_id: "Game001".to_string(),
             team_id: "Team003".to_string(),
-            team_rank: 2u64,
+            team_rank: 1u64,
             team_points: 300u64,
             reward_amount: Uint128::from(300u128),
             refund_amount: Uint128::from(INITIAL_REFUND_AMOUNT),
@@ -2740,7 +3004,7 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string());
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -2760,6 +3024,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results.clone(),
         );
@@ -2772,7 +3037,7 @@ mod tests {
             }
         }
 
-        let query_game_status_res = query_game_details(&mut deps.storage);
+        let query_game_status_res = query_game_details(&mut deps.storage, "Game001".to_string(), mock_env().block.time);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -2782,7 +3047,7 @@ mod tests {
                 assert_eq!(5, 6);
             }
         }
-        let team_details = POOL_TEAM_DETAILS.load(&mut deps.storage, pool_id_1.clone());
+        let team_details = query_pool_teams(&deps.storage, pool_id_1.clone());
         for team in team_details {
             assert_eq!(team[0].reward_amount, Uint128::from(100u128));
             assert_eq!(team[1].reward_amount, Uint128::from(200u128));
@@ -2793,6 +3058,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
         );
@@ -2823,9 +3089,9 @@ mod tests {
             minting_contract_address: "cwtoken11111".to_string(),
             admin_address: "admin11111".to_string(),
             platform_fee: platform_fee,
-            game_id: "Game001".to_string(),
             platform_fees_collector_wallet: "FEE_WALLET".to_string(),
             astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
         };
 
         let adminInfo = mock_info("admin11111", &[]);
@@ -2860,5 +3126,1983 @@ mod tests {
             assert_eq!(wallet.wallet_name, "rake_3".to_string());
         }
     }
-}
 
+    #[test]
+    fn test_multiple_games() {
+        let mut deps = mock_dependencies(&[]);
+        let platform_fee = Uint128::from(300000u128);
+        let transaction_fee = Uint128::from(100000u128);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: transaction_fee,
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: platform_fee,
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        let lock_at = mock_env().block.time.plus_seconds(60 * 60);
+        for game_id in ["Game001", "Game002"] {
+            create_game(
+                deps.as_mut(),
+                mock_env(),
+                adminInfo.clone(),
+                game_id.to_string(),
+                Some(lock_at),
+                None,
+            )
+            .unwrap();
+        }
+        let err = create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Game already exists")));
+        let err = create_game(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("Gamer001", &[]),
+            "Game003".to_string(),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized { invoker: "Gamer001".to_string() });
+        assert_eq!(query_all_games(&deps.storage, mock_env().block.time).unwrap().len(), 2);
+
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            1,
+            10,
+            2,
+            vec![],
+        )
+        .unwrap();
+        let mut pool_ids = Vec::new();
+        for game_id in ["Game001", "Game002", "Game002"] {
+            let rsp = create_pool(
+                deps.as_mut(),
+                mock_env(),
+                adminInfo.clone(),
+                game_id.to_string(),
+                "oneToOne".to_string(),
+            )
+            .unwrap();
+            pool_ids.push(rsp.attributes[0].value.clone());
+        }
+        assert_eq!(query_all_pools_in_game(&deps.storage, "Game001".to_string()).unwrap().len(), 1);
+        assert_eq!(query_all_pools_in_game(&deps.storage, "Game002".to_string()).unwrap().len(), 2);
+
+        // locking one game leaves the other open for bidding
+        lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string()).unwrap();
+        let game_1 = query_game_details(&deps.storage, "Game001".to_string(), mock_env().block.time).unwrap();
+        assert_eq!(game_1.game_status, GAME_POOL_CLOSED);
+        let game_2 = query_game_details(&deps.storage, "Game002".to_string(), mock_env().block.time).unwrap();
+        assert_eq!(game_2.game_status, GAME_POOL_OPEN);
+        assert_eq!(game_2.lock_at, Some(lock_at));
+
+        // a pool is only distributed as part of its own game
+        let err = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_ids[1].clone(),
+            vec![],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Pool does not belong to the game")));
+
+        // no bids once the game is past its lock_at time
+        let mut later = mock_env();
+        later.block.time = lock_at;
+        let err = game_pool_bid_submit(
+            deps.as_mut(),
+            later,
+            mock_info("cwtoken11111", &[]),
+            "Gamer001".to_string(),
+            "oneToOne".to_string(),
+            pool_ids[1].clone(),
+            "Team001".to_string(),
+            Uint128::from(144262u128),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Game is not open for bidding")));
+    }
+
+    #[test]
+    fn test_migrate_single_game_instance() {
+        let mut deps = mock_dependencies(&[]);
+        let platform_fee = Uint128::from(300000u128);
+        let transaction_fee = Uint128::from(100000u128);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: transaction_fee,
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: platform_fee,
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        // state as left by the single game version
+        LEGACY_CONFIG
+            .save(
+                &mut deps.storage,
+                &LegacyConfig {
+                    admin_address: Addr::unchecked("admin11111"),
+                    minting_contract_address: Addr::unchecked("cwtoken11111"),
+                    platform_fees_collector_wallet: Addr::unchecked("FEE_WALLET"),
+                    astro_proxy_address: Addr::unchecked("ASTROPORT"),
+                    platform_fee: platform_fee,
+                    transaction_fee: transaction_fee,
+                    game_id: "Game001".to_string(),
+                },
+            )
+            .unwrap();
+        // a pool saved before pools were indexed by game
+        let legacy_pools: Map<String, PoolDetails> = Map::new("pool_details");
+        legacy_pools
+            .save(
+                &mut deps.storage,
+                "1".to_string(),
+                &PoolDetails {
+                    pool_id: "1".to_string(),
+                    game_id: "Game001".to_string(),
+                    pool_type: "oneToOne".to_string(),
+                    current_teams_count: 0u32,
+                    rewards_distributed: REWARDS_NOT_DISTRIBUTED,
+                },
+            )
+            .unwrap();
+        assert_eq!(query_all_pools_in_game(&deps.storage, "Game001".to_string()).unwrap().len(), 0);
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        let pools = query_all_pools_in_game(&deps.storage, "Game001".to_string()).unwrap();
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].pool_id, "1".to_string());
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.admin_address, Addr::unchecked("admin11111"));
+        let game = query_game_details(&deps.storage, "Game001".to_string(), mock_env().block.time).unwrap();
+        assert_eq!(game.game_status, GAME_POOL_OPEN);
+        assert_eq!(game.lock_at, None);
+        create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "oneToOne".to_string(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_team_details_cannot_be_written_by_gamers() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100000u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            1,
+            10,
+            2,
+            vec![],
+        )
+        .unwrap();
+        create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "oneToOne".to_string(),
+        )
+        .unwrap();
+
+        // a team with a reward of its own choosing is no longer accepted
+        let attack = br#"{"save_team_details":{"gamer":"Gamer001","pool_id":"1","team_id":"Team001","game_id":"Game001","pool_type":"oneToOne","reward_amount":"1000000000","claimed_reward":false,"refund_amount":"0","claimed_refund":false,"team_points":0,"team_rank":1}}"#;
+        assert!(from_binary::<ExecuteMsg>(&Binary::from(&attack[..])).is_err());
+
+        let gamerInfo = mock_info("Gamer001", &[]);
+        let import_msg = ExecuteMsg::ImportTeams {
+            teams: vec![ImportedTeam {
+                gamer: "Gamer001".to_string(),
+                pool_id: "1".to_string(),
+                team_id: "Team001".to_string(),
+            }],
+        };
+        let err = execute(deps.as_mut(), mock_env(), gamerInfo.clone(), import_msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized { invoker: "Gamer001".to_string() });
+        assert!(query_pool_teams(&deps.storage, "1".to_string()).unwrap().is_empty());
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            gamerInfo.clone(),
+            ExecuteMsg::ClaimReward { gamer: "Gamer001".to_string() },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("No reward for this user")));
+    }
+
+    #[test]
+    fn test_import_teams() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100000u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            1,
+            3,
+            2,
+            vec![],
+        )
+        .unwrap();
+        create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "oneToOne".to_string(),
+        )
+        .unwrap();
+
+        let team = |gamer: &str, pool_id: &str, team_id: &str| ImportedTeam {
+            gamer: gamer.to_string(),
+            pool_id: pool_id.to_string(),
+            team_id: team_id.to_string(),
+        };
+        let import = |deps: DepsMut, teams: Vec<ImportedTeam>| {
+            let pool_fees = 144262u128 * teams.len() as u128;
+            execute(
+                deps,
+                mock_env(),
+                mock_info("admin11111", &[coin(pool_fees, "uusd")]),
+                ExecuteMsg::ImportTeams { teams },
+            )
+        };
+
+        let err = import(deps.as_mut(), vec![team("Gamer001", "2", "Team001")]).unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("No pool details found")));
+
+        // the teams are paid for like a bid would pay for them
+        let teams = vec![
+            team("Gamer001", "1", "Team001"),
+            team("Gamer001", "1", "Team002"),
+            team("Gamer002", "1", "Team003"),
+        ];
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin11111", &[coin(144262u128 * 2, "uusd")]),
+            ExecuteMsg::ImportTeams { teams: teams.clone() },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::generic_err("Pool fees of 432786 uusd must be sent with the teams"))
+        );
+        let rsp = import(deps.as_mut(), teams).unwrap();
+        assert_eq!(rsp.attributes[1].value, "3".to_string());
+        let pool = query_pool_details(&deps.storage, "1".to_string()).unwrap();
+        assert_eq!(pool.current_teams_count, 3u32);
+        let imported = query_team_details(&deps.storage, "1".to_string(), "Team003".to_string()).unwrap();
+        assert_eq!(imported.gamer_address, "Gamer002".to_string());
+        assert_eq!(imported.game_id, "Game001".to_string());
+        assert_eq!(imported.pool_type, "oneToOne".to_string());
+        assert_eq!(imported.reward_amount, Uint128::zero());
+
+        // the pool is full
+        let err = import(deps.as_mut(), vec![team("Gamer003", "1", "Team004")]).unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("pool max team limit reached ")));
+
+        // a gamer gets no more teams than a bid would give it
+        create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "oneToOne".to_string(),
+        )
+        .unwrap();
+        let err = import(
+            deps.as_mut(),
+            vec![
+                team("Gamer003", "2", "Team004"),
+                team("Gamer003", "2", "Team005"),
+                team("Gamer003", "2", "Team006"),
+            ],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("User max team limit reached ")));
+
+        // and nothing is imported once the game is locked
+        lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string()).unwrap();
+        let err = import(deps.as_mut(), vec![team("Gamer003", "2", "Team004")]).unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Game is not open for bidding")));
+    }
+
+    #[test]
+    fn test_cancel_after_import_refunds_what_was_paid() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100000u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(deps.as_mut(), mock_env(), adminInfo.clone(), instantiate_msg).unwrap();
+        create_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), None, None)
+            .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            1,
+            3,
+            2,
+            vec![],
+        )
+        .unwrap();
+        create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "oneToOne".to_string(),
+        )
+        .unwrap();
+
+        let teams = vec![
+            ImportedTeam {
+                gamer: "Gamer001".to_string(),
+                pool_id: "1".to_string(),
+                team_id: "Team001".to_string(),
+            },
+            ImportedTeam {
+                gamer: "Gamer002".to_string(),
+                pool_id: "1".to_string(),
+                team_id: "Team002".to_string(),
+            },
+        ];
+        let paid = Uint128::from(144262u128 * 2);
+        // importing for free is turned away
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            ExecuteMsg::ImportTeams { teams: teams.clone() },
+        );
+        assert!(err.is_err());
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin11111", &[coin(paid.u128(), "uusd")]),
+            ExecuteMsg::ImportTeams { teams },
+        )
+        .unwrap();
+
+        cancel_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string()).unwrap();
+        let now = mock_env().block.time;
+        let refunds = query_refund(&deps.storage, "Gamer001".to_string(), "Game001".to_string(), now).unwrap()
+            + query_refund(&deps.storage, "Gamer002".to_string(), "Game001".to_string(), now).unwrap();
+        assert_eq!(refunds, paid);
+    }
+
+    #[test]
+    fn test_rake_and_platform_fee_ledger() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(100u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        let wallet = |name: &str, percentage: u32| WalletPercentage {
+            wallet_address: name.to_string(),
+            wallet_name: name.to_string(),
+            percentage,
+        };
+
+        let err = set_platform_fee_wallets(
+            deps.as_mut(),
+            adminInfo.clone(),
+            vec![wallet("dev_wallet", 60), wallet("refund_wallet", 50)],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Platform wallet percentages exceed 100")));
+        set_platform_fee_wallets(
+            deps.as_mut(),
+            adminInfo.clone(),
+            vec![wallet("dev_wallet", 60), wallet("refund_wallet", 30)],
+        )
+        .unwrap();
+
+        let err = set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            1,
+            3,
+            3,
+            vec![wallet("rake_1", 90), wallet("rake_2", 20)],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Rake percentages must be below 100")));
+        // a rake of all the collection would leave no prize
+        let err = set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            1,
+            3,
+            3,
+            vec![wallet("rake_1", 90), wallet("rake_2", 10)],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Rake percentages must be below 100")));
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            1,
+            3,
+            3,
+            vec![wallet("rake_1", 1), wallet("rake_2", 2), wallet("rake_3", 3)],
+        )
+        .unwrap();
+        create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "oneToOne".to_string(),
+        )
+        .unwrap();
+
+        let team_ids = ["Team001", "Team002", "Team003"];
+        let teams = team_ids
+            .iter()
+            .map(|team_id| ImportedTeam {
+                gamer: "Gamer001".to_string(),
+                pool_id: "1".to_string(),
+                team_id: team_id.to_string(),
+            })
+            .collect();
+        execute(deps.as_mut(), mock_env(), mock_info("admin11111", &[coin(432786, "uusd")]), ExecuteMsg::ImportTeams { teams }).unwrap();
+        lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string()).unwrap();
+
+        let game_winners = team_ids
+            .iter()
+            .enumerate()
+            .map(|(rank, team_id)| GameResult {
+                gamer_address: "Gamer001".to_string(),
+                game_id: "Game001".to_string(),
+                team_id: team_id.to_string(),
+                reward_amount: Uint128::from(100u128 * (rank as u128 + 1)),
+                refund_amount: Uint128::zero(),
+                team_rank: 3 - rank as u64,
+                team_points: 100,
+            })
+            .collect();
+        game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "1".to_string(),
+            game_winners,
+        )
+        .unwrap();
+
+        // 3 teams of 144262 less 600 in rewards leaves a rake of 432186, of which
+        // the rake wallets take 1%, 2% and 3% of the 432786 collected.
+        // 1% of each pool fee gives a platform fee of 3 * 1442.
+        // The dust and the unallocated percentages go to the collector wallet
+        let entry = |wallet: &str, credited: u128| RakeLedgerEntry {
+            wallet_address: wallet.to_string(),
+            credited: Uint128::from(credited),
+            claimed: Uint128::zero(),
+        };
+        let ledger = query_rake_ledger(&deps.storage).unwrap();
+        assert_eq!(
+            ledger,
+            vec![
+                entry("FEE_WALLET", 406221 + 434),
+                entry("dev_wallet", 2595),
+                entry("rake_1", 4327),
+                entry("rake_2", 8655),
+                entry("rake_3", 12983),
+                entry("refund_wallet", 1297),
+            ]
+        );
+        let total_credited: Uint128 = ledger.iter().map(|e| e.credited).sum();
+        assert_eq!(total_credited, Uint128::from(432186u128 + 4326u128));
+
+        // Anyone can trigger the claim, the funds go to the wallet
+        let claim = |deps: DepsMut, wallet: &str| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("Gamer001", &[]),
+                ExecuteMsg::ClaimRake { wallet: wallet.to_string() },
+            )
+        };
+        let rsp = claim(deps.as_mut(), "rake_1").unwrap();
+        assert_eq!(
+            rsp.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "rake_1".to_string(),
+                amount: vec![coin(4327, "uusd")],
+            })
+        );
+        let err = claim(deps.as_mut(), "rake_1").unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("No rake for this wallet")));
+        let err = claim(deps.as_mut(), "Gamer001").unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("No rake for this wallet")));
+
+        let ledger = query_rake_ledger(&deps.storage).unwrap();
+        assert_eq!(ledger[2].claimed, Uint128::from(4327u128));
+        assert_eq!(ledger[2].credited, Uint128::from(4327u128));
+    }
+
+    #[test]
+    fn test_game_winners_are_validated() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(100u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(deps.as_mut(), mock_env(), adminInfo.clone(), instantiate_msg).unwrap();
+        create_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), None, None).unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            1,
+            3,
+            3,
+            vec![],
+        )
+        .unwrap();
+        create_pool(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), "oneToOne".to_string()).unwrap();
+        let teams = vec![
+            ImportedTeam { gamer: "Gamer001".to_string(), pool_id: "1".to_string(), team_id: "Team001".to_string() },
+            ImportedTeam { gamer: "Gamer001".to_string(), pool_id: "1".to_string(), team_id: "Team002".to_string() },
+            ImportedTeam { gamer: "Gamer002".to_string(), pool_id: "1".to_string(), team_id: "Team003".to_string() },
+        ];
+        execute(deps.as_mut(), mock_env(), mock_info("admin11111", &[coin(3000, "uusd")]), ExecuteMsg::ImportTeams { teams }).unwrap();
+        lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string()).unwrap();
+
+        let result = |gamer: &str, team_id: &str, team_rank: u64, reward: u128| GameResult {
+            gamer_address: gamer.to_string(),
+            game_id: "Game001".to_string(),
+            team_id: team_id.to_string(),
+            reward_amount: Uint128::from(reward),
+            refund_amount: Uint128::zero(),
+            team_rank,
+            team_points: 100,
+        };
+        let distribute = |deps: DepsMut, game_winners: Vec<GameResult>| {
+            game_pool_reward_distribute(
+                deps,
+                mock_env(),
+                mock_info("admin11111", &[]),
+                "Game001".to_string(),
+                "1".to_string(),
+                game_winners,
+            )
+        };
+        let expect_err = |deps: DepsMut, game_winners: Vec<GameResult>, msg: &str| {
+            let err = distribute(deps, game_winners).unwrap_err();
+            assert_eq!(err, ContractError::Std(StdError::generic_err(msg)));
+        };
+
+        // team of another gamer
+        expect_err(
+            deps.as_mut(),
+            vec![result("Gamer002", "Team001", 1, 100)],
+            "Winner is not a team in this pool",
+        );
+        expect_err(
+            deps.as_mut(),
+            vec![result("Gamer001", "Team001", 1, 200), result("Gamer001", "Team001", 2, 100)],
+            "Winner appears more than once",
+        );
+        expect_err(
+            deps.as_mut(),
+            vec![result("Gamer001", "Team001", 0, 100)],
+            "Winner ranks start at 1",
+        );
+        expect_err(
+            deps.as_mut(),
+            vec![result("Gamer001", "Team001", 1, 200), result("Gamer002", "Team003", 1, 100)],
+            "Winner ranks must be unique",
+        );
+        expect_err(
+            deps.as_mut(),
+            vec![result("Gamer001", "Team001", 2, 200), result("Gamer002", "Team003", 1, 100)],
+            "Rewards must not increase with rank",
+        );
+        // nothing was recorded for the rejected results
+        let pool = query_pool_details(&deps.storage, "1".to_string()).unwrap();
+        assert_eq!(pool.rewards_distributed, REWARDS_NOT_DISTRIBUTED);
+
+        // winners may be listed in any order
+        distribute(
+            deps.as_mut(),
+            vec![result("Gamer002", "Team003", 2, 100), result("Gamer001", "Team001", 1, 200)],
+        )
+        .unwrap();
+        let winner = query_team_details(&deps.storage, "1".to_string(), "Team003".to_string()).unwrap();
+        assert_eq!(winner.reward_amount, Uint128::from(100u128));
+        assert_eq!(winner.team_rank, 2u64);
+    }
+
+    #[test]
+    fn test_payout_table_rewards() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(100u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(deps.as_mut(), mock_env(), adminInfo.clone(), instantiate_msg).unwrap();
+        create_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), None, None).unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "multiTeam".to_string(),
+            Uint128::from(1000u128),
+            1,
+            4,
+            4,
+            vec![WalletPercentage {
+                wallet_address: "RAKE_WALLET".to_string(),
+                wallet_name: "rake".to_string(),
+                percentage: 10,
+            }],
+        )
+        .unwrap();
+
+        let err = set_pool_type_payout_table(
+            deps.as_mut(),
+            mock_info("Gamer001", &[]),
+            "multiTeam".to_string(),
+            vec![50, 30, 10],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized { invoker: "Gamer001".to_string() });
+        let err = set_pool_type_payout_table(deps.as_mut(), adminInfo.clone(), "multiTeam".to_string(), vec![50, 30, 30])
+            .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Payout table percentages exceed 100")));
+        let err = set_pool_type_payout_table(deps.as_mut(), adminInfo.clone(), "multiTeam".to_string(), vec![30, 50, 10])
+            .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Payout table percentages must not increase with rank")));
+        set_pool_type_payout_table(deps.as_mut(), adminInfo.clone(), "multiTeam".to_string(), vec![50, 30, 20]).unwrap();
+        // the params of the pool type cannot be set again and wipe the table
+        let err = set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "multiTeam".to_string(),
+            Uint128::from(1000u128),
+            1,
+            4,
+            4,
+            vec![],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Pool type already set")));
+        let pool_type = query_pool_type_details(&deps.storage, "multiTeam".to_string()).unwrap();
+        assert_eq!(pool_type.payout_table, vec![50, 30, 20]);
+
+        create_pool(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), "multiTeam".to_string()).unwrap();
+        let teams = ["Team001", "Team002", "Team003", "Team004"]
+            .iter()
+            .map(|team_id| ImportedTeam {
+                gamer: "Gamer001".to_string(),
+                pool_id: "1".to_string(),
+                team_id: team_id.to_string(),
+            })
+            .collect();
+        execute(deps.as_mut(), mock_env(), mock_info("admin11111", &[coin(4000, "uusd")]), ExecuteMsg::ImportTeams { teams }).unwrap();
+        lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string()).unwrap();
+
+        let result = |team_id: &str, team_rank: u64, reward: u128| GameResult {
+            gamer_address: "Gamer001".to_string(),
+            game_id: "Game001".to_string(),
+            team_id: team_id.to_string(),
+            reward_amount: Uint128::from(reward),
+            refund_amount: Uint128::zero(),
+            team_rank,
+            team_points: 100 - team_rank,
+        };
+        let err = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "1".to_string(),
+            vec![result("Team001", 1, 2000)],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Rewards are computed from the payout table")));
+
+        // Only ranks and points are given, 4000 collected less a rake of 10% pays 1800/1080/720
+        let rsp = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "1".to_string(),
+            vec![
+                result("Team003", 1, 0),
+                result("Team001", 2, 0),
+                result("Team004", 3, 0),
+                result("Team002", 4, 0),
+            ],
+        )
+        .unwrap();
+        assert_eq!(rsp.attributes[4].value, "400".to_string());
+        let rewards: Vec<(String, Uint128, u64)> = query_pool_teams(&deps.storage, "1".to_string())
+            .unwrap()
+            .into_iter()
+            .map(|team| (team.team_id, team.reward_amount, team.team_points))
+            .collect();
+        assert_eq!(
+            rewards,
+            vec![
+                ("Team001".to_string(), Uint128::from(1080u128), 98),
+                ("Team002".to_string(), Uint128::zero(), 96),
+                ("Team003".to_string(), Uint128::from(1800u128), 99),
+                ("Team004".to_string(), Uint128::from(720u128), 97),
+            ]
+        );
+
+        // The rake wallet takes its 10% of the collection, which is all of the rake,
+        // and the collector wallet only gets the platform fees
+        let ledger: Vec<(String, Uint128)> = query_rake_ledger(&deps.storage)
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.wallet_address, entry.credited))
+            .collect();
+        assert_eq!(
+            ledger,
+            vec![
+                ("FEE_WALLET".to_string(), Uint128::from(40u128)),
+                ("RAKE_WALLET".to_string(), Uint128::from(400u128)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pool_type_bid_allocates_pools() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100000u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(deps.as_mut(), mock_env(), adminInfo.clone(), instantiate_msg).unwrap();
+        create_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), None, None).unwrap();
+        for pool_type in ["twoTeams", "multiTeam"] {
+            set_pool_type_params(
+                deps.as_mut(),
+                mock_env(),
+                adminInfo.clone(),
+                pool_type.to_string(),
+                Uint128::from(144262u128),
+                1,
+                2,
+                1,
+                vec![],
+            )
+            .unwrap();
+        }
+        // an open pool of another type is not picked
+        create_pool(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), "multiTeam".to_string()).unwrap();
+
+        let bid = |deps: DepsMut, gamer: &str, pool_type: &str, team_id: &str| {
+            // The bid goes on to query the swap fee from the proxy, which the
+            // mock querier does not answer, so only the allocation is checked
+            let _ = game_pool_type_bid_submit(
+                deps,
+                mock_env(),
+                mock_info(gamer, &[coin(10000000, "uusd")]),
+                gamer.to_string(),
+                "Game001".to_string(),
+                pool_type.to_string(),
+                team_id.to_string(),
+                Uint128::from(144262u128),
+                None,
+                None,
+                None,
+            );
+        };
+        let pool_of = |storage: &dyn Storage, team_id: &str| {
+            query_all_teams(storage)
+                .unwrap()
+                .into_iter()
+                .find(|team| team.team_id == team_id)
+                .map(|team| team.pool_id)
+                .unwrap()
+        };
+
+        bid(deps.as_mut(), "Gamer001", "twoTeams", "Team001");
+        assert_eq!(pool_of(&deps.storage, "Team001"), "2".to_string());
+        // Gamer001 is at its limit in pool 2
+        bid(deps.as_mut(), "Gamer001", "twoTeams", "Team002");
+        assert_eq!(pool_of(&deps.storage, "Team002"), "3".to_string());
+        bid(deps.as_mut(), "Gamer002", "twoTeams", "Team003");
+        assert_eq!(pool_of(&deps.storage, "Team003"), "2".to_string());
+        bid(deps.as_mut(), "Gamer003", "twoTeams", "Team004");
+        assert_eq!(pool_of(&deps.storage, "Team004"), "3".to_string());
+        bid(deps.as_mut(), "Gamer004", "twoTeams", "Team005");
+        assert_eq!(pool_of(&deps.storage, "Team005"), "4".to_string());
+
+        let pools = query_all_pools_in_game(&deps.storage, "Game001".to_string()).unwrap();
+        let counts: Vec<(String, String, u32)> = pools
+            .into_iter()
+            .map(|pool| (pool.pool_id, pool.pool_type, pool.current_teams_count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("1".to_string(), "multiTeam".to_string(), 0),
+                ("2".to_string(), "twoTeams".to_string(), 2),
+                ("3".to_string(), "twoTeams".to_string(), 2),
+                ("4".to_string(), "twoTeams".to_string(), 1),
+            ]
+        );
+
+        let err = game_pool_type_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("Gamer001", &[coin(10000000, "uusd")]),
+            "Gamer001".to_string(),
+            "Game001".to_string(),
+            "fiftyTeams".to_string(),
+            "Team006".to_string(),
+            Uint128::from(144262u128),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("No pool type details found")));
+
+        // a bid into a pool names the type of that pool
+        let err = game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("Gamer005", &[coin(10000000, "uusd")]),
+            "Gamer005".to_string(),
+            "multiTeam".to_string(),
+            "4".to_string(),
+            "Team006".to_string(),
+            Uint128::from(144262u128),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Pool type does not match the pool")));
+        let pool = query_pool_details(&deps.storage, "4".to_string()).unwrap();
+        assert_eq!(pool.pool_type, "twoTeams".to_string());
+        assert_eq!(pool.current_teams_count, 1);
+        let pools = pool_details()
+            .idx
+            .game_pool_type
+            .prefix(("Game001".to_string(), "multiTeam".to_string()))
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .count();
+        assert_eq!(pools, 1);
+    }
+
+    #[test]
+    fn test_lock_at_and_settle_deadline() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100000u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(deps.as_mut(), mock_env(), adminInfo.clone(), instantiate_msg).unwrap();
+
+        let lock_at = mock_env().block.time.plus_seconds(60 * 60);
+        let settle_deadline = lock_at.plus_seconds(60 * 60);
+        let err = create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            Some(lock_at),
+            Some(lock_at),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Settle deadline must be after the game is locked")));
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            Some(lock_at),
+            Some(settle_deadline),
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            1,
+            2,
+            1,
+            vec![],
+        )
+        .unwrap();
+        for _ in 0..2 {
+            create_pool(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), "oneToOne".to_string()).unwrap();
+        }
+        let teams = vec![
+            ImportedTeam { gamer: "Gamer001".to_string(), pool_id: "1".to_string(), team_id: "Team001".to_string() },
+            ImportedTeam { gamer: "Gamer002".to_string(), pool_id: "1".to_string(), team_id: "Team002".to_string() },
+            ImportedTeam { gamer: "Gamer001".to_string(), pool_id: "2".to_string(), team_id: "Team003".to_string() },
+            ImportedTeam { gamer: "Gamer003".to_string(), pool_id: "2".to_string(), team_id: "Team004".to_string() },
+        ];
+        execute(deps.as_mut(), mock_env(), mock_info("admin11111", &[coin(577048, "uusd")]), ExecuteMsg::ImportTeams { teams }).unwrap();
+
+        // the game locks itself without the admin
+        let mut locked = mock_env();
+        locked.block.time = lock_at;
+        let game = query_game_details(&deps.storage, "Game001".to_string(), locked.block.time).unwrap();
+        assert_eq!(game.game_status, GAME_POOL_CLOSED);
+        let err = execute(
+            deps.as_mut(),
+            locked.clone(),
+            mock_info("admin11111", &[coin(144262, "uusd")]),
+            ExecuteMsg::ImportTeams {
+                teams: vec![ImportedTeam { gamer: "Gamer004".to_string(), pool_id: "2".to_string(), team_id: "Team005".to_string() }],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Game is not open for bidding")));
+
+        // pool 1 is distributed in time, pool 2 is not
+        let winner = GameResult {
+            gamer_address: "Gamer001".to_string(),
+            game_id: "Game001".to_string(),
+            team_id: "Team001".to_string(),
+            reward_amount: Uint128::from(200000u128),
+            refund_amount: Uint128::zero(),
+            team_rank: 1,
+            team_points: 100,
+        };
+        game_pool_reward_distribute(
+            deps.as_mut(),
+            locked.clone(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "1".to_string(),
+            vec![winner],
+        )
+        .unwrap();
+        // pool 2 still has to be distributed
+        let game = query_game_details(&deps.storage, "Game001".to_string(), locked.block.time).unwrap();
+        assert_eq!(game.game_status, GAME_POOL_CLOSED);
+
+        let mut settled = mock_env();
+        settled.block.time = settle_deadline;
+        let err = game_pool_reward_distribute(
+            deps.as_mut(),
+            settled.clone(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "2".to_string(),
+            vec![],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Rewards cant be distributed after the settle deadline")));
+        let game = query_game_details(&deps.storage, "Game001".to_string(), settled.block.time).unwrap();
+        assert_eq!(game.game_status, GAME_CANCELLED);
+
+        // everyone in pool 2 gets a full refund, nobody in pool 1 does
+        assert_eq!(query_refund(&deps.storage, "Gamer003".to_string(), "Game001".to_string(), locked.block.time).unwrap(), Uint128::zero());
+        assert_eq!(query_refund(&deps.storage, "Gamer003".to_string(), "Game001".to_string(), settled.block.time).unwrap(), Uint128::from(144262u128));
+        assert_eq!(query_refund(&deps.storage, "Gamer001".to_string(), "Game001".to_string(), settled.block.time).unwrap(), Uint128::from(144262u128));
+        assert_eq!(query_refund(&deps.storage, "Gamer002".to_string(), "Game001".to_string(), settled.block.time).unwrap(), Uint128::zero());
+
+        // The refund transfer queries the swap fee from the proxy, which the
+        // mock querier does not answer, so only the bookkeeping is checked
+        let _ = claim_refund(deps.as_mut(), mock_info("Gamer003", &[]), "Gamer003".to_string(), settled.clone());
+        let refunded = query_team_details(&deps.storage, "2".to_string(), "Team004".to_string()).unwrap();
+        assert_eq!(refunded.refund_amount, Uint128::from(144262u128));
+        assert_eq!(refunded.claimed_refund, CLAIMED_REFUND);
+        assert_eq!(query_refund(&deps.storage, "Gamer003".to_string(), "Game001".to_string(), settled.block.time).unwrap(), Uint128::zero());
+    }
+
+    #[test]
+    fn test_game_completes_once_every_pool_is_distributed() {
+        let setup = || {
+            let mut deps = mock_dependencies(&[]);
+            let instantiate_msg = InstantiateMsg {
+                transaction_fee: Uint128::from(100u128),
+                minting_contract_address: "cwtoken11111".to_string(),
+                admin_address: "admin11111".to_string(),
+                platform_fee: Uint128::from(100u128),
+                platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+                astro_proxy_address: "ASTROPORT".to_string(),
+                price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+            };
+            let adminInfo = mock_info("admin11111", &[]);
+            instantiate(deps.as_mut(), mock_env(), adminInfo.clone(), instantiate_msg).unwrap();
+            create_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), None, None).unwrap();
+            set_pool_type_params(
+                deps.as_mut(),
+                mock_env(),
+                adminInfo.clone(),
+                "oneToOne".to_string(),
+                Uint128::from(1000u128),
+                2,
+                2,
+                1,
+                vec![],
+            )
+            .unwrap();
+            // pool 3 is left without teams and is refunded instead
+            for _ in 0..3 {
+                create_pool(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), "oneToOne".to_string()).unwrap();
+            }
+            let teams = vec![
+                ImportedTeam { gamer: "Gamer001".to_string(), pool_id: "1".to_string(), team_id: "Team001".to_string() },
+                ImportedTeam { gamer: "Gamer002".to_string(), pool_id: "1".to_string(), team_id: "Team002".to_string() },
+                ImportedTeam { gamer: "Gamer003".to_string(), pool_id: "2".to_string(), team_id: "Team003".to_string() },
+                ImportedTeam { gamer: "Gamer004".to_string(), pool_id: "2".to_string(), team_id: "Team004".to_string() },
+            ];
+            execute(deps.as_mut(), mock_env(), mock_info("admin11111", &[coin(4000, "uusd")]), ExecuteMsg::ImportTeams { teams }).unwrap();
+            lock_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string()).unwrap();
+            deps
+        };
+        let winner = |gamer: &str, team_id: &str| GameResult {
+            gamer_address: gamer.to_string(),
+            game_id: "Game001".to_string(),
+            team_id: team_id.to_string(),
+            reward_amount: Uint128::from(1500u128),
+            refund_amount: Uint128::zero(),
+            team_rank: 1,
+            team_points: 100,
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+
+        let mut deps = setup();
+        let rsp = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "1".to_string(),
+            vec![winner("Gamer001", "Team001")],
+        )
+        .unwrap();
+        assert_eq!(rsp.attributes[0].value, "GAME_POOL_CLOSED".to_string());
+        let game = query_game_details(&deps.storage, "Game001".to_string(), mock_env().block.time).unwrap();
+        assert_eq!(game.game_status, GAME_POOL_CLOSED);
+        let rsp = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "2".to_string(),
+            vec![winner("Gamer003", "Team003")],
+        )
+        .unwrap();
+        assert_eq!(rsp.attributes[0].value, "GAME_COMPLETED".to_string());
+        let game = query_game_details(&deps.storage, "Game001".to_string(), mock_env().block.time).unwrap();
+        assert_eq!(game.game_status, GAME_COMPLETED);
+
+        // Cancelling the game part way refunds only the pools not yet distributed
+        let mut deps = setup();
+        game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "1".to_string(),
+            vec![winner("Gamer001", "Team001")],
+        )
+        .unwrap();
+        cancel_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string()).unwrap();
+        assert_eq!(query_reward(&deps.storage, "Gamer001".to_string(), "Game001".to_string()).unwrap(), Uint128::from(1500u128));
+        assert_eq!(query_refund(&deps.storage, "Gamer001".to_string(), "Game001".to_string(), mock_env().block.time).unwrap(), Uint128::zero());
+        assert_eq!(query_refund(&deps.storage, "Gamer002".to_string(), "Game001".to_string(), mock_env().block.time).unwrap(), Uint128::zero());
+        assert_eq!(query_refund(&deps.storage, "Gamer003".to_string(), "Game001".to_string(), mock_env().block.time).unwrap(), Uint128::from(1000u128));
+        assert_eq!(query_refund(&deps.storage, "Gamer003".to_string(), "Game002".to_string(), mock_env().block.time).unwrap(), Uint128::zero());
+    }
+
+    /// Counts the writes made through it to the storage it wraps
+    struct WriteCounter<'a> {
+        storage: &'a mut MockStorage,
+        writes: usize,
+    }
+
+    impl Storage for WriteCounter<'_> {
+        fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+            self.storage.get(key)
+        }
+
+        fn range<'a>(
+            &'a self,
+            start: Option<&[u8]>,
+            end: Option<&[u8]>,
+            order: Order,
+        ) -> Box<dyn Iterator<Item = Pair> + 'a> {
+            self.storage.range(start, end, order)
+        }
+
+        fn set(&mut self, key: &[u8], value: &[u8]) {
+            self.writes += 1;
+            self.storage.set(key, value)
+        }
+
+        fn remove(&mut self, key: &[u8]) {
+            self.writes += 1;
+            self.storage.remove(key)
+        }
+    }
+
+    #[test]
+    fn test_pool_short_of_teams_at_lock_at_is_refunded() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100000u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(deps.as_mut(), mock_env(), adminInfo.clone(), instantiate_msg).unwrap();
+        let lock_at = mock_env().block.time.plus_seconds(60 * 60);
+        create_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), Some(lock_at), None)
+            .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "threeTeams".to_string(),
+            Uint128::from(144262u128),
+            2,
+            3,
+            1,
+            vec![],
+        )
+        .unwrap();
+        create_pool(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), "threeTeams".to_string())
+            .unwrap();
+        let teams = vec![ImportedTeam {
+            gamer: "Gamer001".to_string(),
+            pool_id: "1".to_string(),
+            team_id: "Team001".to_string(),
+        }];
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin11111", &[coin(144262, "uusd")]),
+            ExecuteMsg::ImportTeams { teams },
+        )
+        .unwrap();
+        // nothing is due while the game is open
+        let refund = query_refund(&deps.storage, "Gamer001".to_string(), "Game001".to_string(), mock_env().block.time).unwrap();
+        assert_eq!(refund, Uint128::zero());
+
+        // the game locks itself without LockGame and the pool is one team short
+        let mut locked = mock_env();
+        locked.block.time = lock_at;
+        let refund = query_refund(&deps.storage, "Gamer001".to_string(), "Game001".to_string(), locked.block.time).unwrap();
+        assert_eq!(refund, Uint128::from(144262u128));
+        let err = game_pool_reward_distribute(
+            deps.as_mut(),
+            locked.clone(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            "1".to_string(),
+            vec![GameResult {
+                gamer_address: "Gamer001".to_string(),
+                game_id: "Game001".to_string(),
+                team_id: "Team001".to_string(),
+                reward_amount: Uint128::from(100000u128),
+                refund_amount: Uint128::zero(),
+                team_rank: 1,
+                team_points: 100,
+            }],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Pool does not have the minimum number of teams")));
+    }
+
+    #[test]
+    fn test_claims_touch_only_the_gamers_teams() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100000u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(deps.as_mut(), mock_env(), adminInfo.clone(), instantiate_msg).unwrap();
+        create_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), None, None).unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "big".to_string(),
+            Uint128::from(1000u128),
+            1,
+            30,
+            30,
+            vec![],
+        )
+        .unwrap();
+
+        // 3000 teams in 100 pools, 3 teams in different pools for each of 1000 gamers
+        for pool in 0..100 {
+            let rsp = create_pool(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), "big".to_string()).unwrap();
+            let pool_id = rsp.attributes[0].value.clone();
+            for slot in 0..30 {
+                let team = pool * 30 + slot;
+                save_team_details(
+                    &mut deps.storage,
+                    mock_env(),
+                    format!("Gamer{:03}", team % 1000),
+                    pool_id.clone(),
+                    format!("Team{:04}", team),
+                    "Game001".to_string(),
+                    "big".to_string(),
+                    Uint128::from(10u128),
+                    UNCLAIMED_REWARD,
+                    Uint128::zero(),
+                    UNCLAIMED_REFUND,
+                    0,
+                    INITIAL_TEAM_RANK,
+                )
+                .unwrap();
+            }
+        }
+        assert_eq!(query_all_teams(&deps.storage).unwrap().len(), 3000);
+        assert_eq!(query_gamer_teams(&deps.storage, "Gamer007".to_string()).unwrap().len(), 3);
+        assert_eq!(
+            get_team_count_for_user_in_pool_type(&deps.storage, "Gamer007".to_string(), "Game001".to_string(), "big".to_string()).unwrap(),
+            3
+        );
+        assert_eq!(
+            query_reward(&deps.storage, "Gamer007".to_string(), "Game001".to_string()).unwrap(),
+            Uint128::from(30u128)
+        );
+
+        // The reward transfer queries the tax, which the mock querier does not
+        // answer, so only the writes to the teams are checked
+        let mut counter = WriteCounter { storage: &mut deps.storage, writes: 0 };
+        let _ = claim_reward(
+            DepsMut {
+                storage: &mut counter,
+                api: &deps.api,
+                querier: QuerierWrapper::new(&deps.querier),
+            },
+            mock_info("Gamer007", &[]),
+            "Gamer007".to_string(),
+            mock_env(),
+        );
+        // a row and its gamer index entry per team of the gamer
+        assert!(counter.writes <= 3 * 3, "{} writes", counter.writes);
+        assert_eq!(query_reward(&deps.storage, "Gamer007".to_string(), "Game001".to_string()).unwrap(), Uint128::zero());
+        assert_eq!(
+            query_reward(&deps.storage, "Gamer008".to_string(), "Game001".to_string()).unwrap(),
+            Uint128::from(30u128)
+        );
+    }
+
+    #[test]
+    fn test_migrate_pool_team_lists() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100000u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin11111", &[]), instantiate_msg).unwrap();
+
+        let team = |pool_id: &str, gamer: &str, team_id: &str| PoolTeamDetails {
+            pool_id: pool_id.to_string(),
+            game_id: "Game001".to_string(),
+            pool_type: "oneToOne".to_string(),
+            gamer_address: gamer.to_string(),
+            team_id: team_id.to_string(),
+            reward_amount: Uint128::zero(),
+            claimed_reward: UNCLAIMED_REWARD,
+            refund_amount: Uint128::zero(),
+            claimed_refund: UNCLAIMED_REFUND,
+            team_points: 0,
+            team_rank: INITIAL_TEAM_RANK,
+        };
+        // state as left by the list per pool version, with a team id bid twice
+        let mut rewarded = team("1", "Gamer001", "Team001");
+        rewarded.reward_amount = Uint128::from(100u128);
+        let mut refunded_twin = team("1", "Gamer001", "Team001");
+        refunded_twin.reward_amount = Uint128::from(20u128);
+        refunded_twin.refund_amount = Uint128::from(50u128);
+        LEGACY_POOL_TEAM_DETAILS
+            .save(
+                &mut deps.storage,
+                "1".to_string(),
+                &vec![rewarded, team("1", "Gamer002", "Team002"), refunded_twin],
+            )
+            .unwrap();
+        LEGACY_POOL_TEAM_DETAILS
+            .save(&mut deps.storage, "2".to_string(), &vec![team("2", "Gamer001", "Team003")])
+            .unwrap();
+
+        let rsp = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert_eq!(rsp.attributes[1].value, "4".to_string());
+        assert!(LEGACY_POOL_TEAM_DETAILS
+            .may_load(&deps.storage, "1".to_string())
+            .unwrap()
+            .is_none());
+
+        let teams = query_gamer_teams(&deps.storage, "Gamer001".to_string()).unwrap();
+        assert_eq!(teams.len(), 2);
+        assert_eq!(teams[0].team_id, "Team001".to_string());
+        assert_eq!(teams[0].reward_amount, Uint128::from(120u128));
+        assert_eq!(teams[0].refund_amount, Uint128::from(50u128));
+        assert_eq!(teams[1].pool_id, "2".to_string());
+        assert_eq!(query_pool_teams(&deps.storage, "1".to_string()).unwrap().len(), 2);
+    }
+
+    /// Answers the price queries of the astro proxy and of an astroport
+    /// oracle, at 2 and 3 FURY per UST, and a swap fee of 1000 uusd
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum MockPriceQueryMsg {
+        GetFuryEquivalentToUst { ust_count: Uint128 },
+        QueryPlatformFees { msg: Binary },
+        Consult { token: AssetInfo, amount: Uint128 },
+    }
+
+    fn mock_proxy_instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum MockProxyExecuteMsg {
+        Swap {
+            offer_asset: Asset,
+            belief_price: Option<Decimal>,
+            max_spread: Option<Decimal>,
+            to: Option<String>,
+        },
+        /// uusd returned per FURY swapped, 0.5 until set
+        SetReturnRate { ust_per_fury: Decimal },
+    }
+
+    const MOCK_RETURN_RATE: Item<Decimal> = Item::new("return_rate");
+
+    // takes the uusd sent with the swap as its fee, and keeps the FURY
+    fn mock_proxy_execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: MockProxyExecuteMsg) -> StdResult<Response> {
+        match msg {
+            MockProxyExecuteMsg::Swap { offer_asset, belief_price, max_spread, to } => {
+                let rate = MOCK_RETURN_RATE.may_load(deps.storage)?.unwrap_or(Decimal::percent(50));
+                let return_amount = offer_asset.amount * rate;
+                // the spread check of an astroport pair given a belief price
+                if let (Some(belief_price), Some(max_spread)) = (belief_price, max_spread) {
+                    let offer_value = offer_asset.amount - offer_asset.amount * max_spread;
+                    if return_amount * belief_price < offer_value {
+                        return Err(StdError::generic_err("Operation exceeds max spread limit"));
+                    }
+                }
+                Ok(Response::new().add_message(BankMsg::Send {
+                    to_address: to.unwrap(),
+                    amount: vec![coin(return_amount.u128(), "uusd")],
+                }))
+            }
+            MockProxyExecuteMsg::SetReturnRate { ust_per_fury } => {
+                MOCK_RETURN_RATE.save(deps.storage, &ust_per_fury)?;
+                Ok(Response::new())
+            }
+        }
+    }
+
+    fn mock_proxy_query(_deps: Deps, _env: Env, msg: MockPriceQueryMsg) -> StdResult<Binary> {
+        match msg {
+            MockPriceQueryMsg::GetFuryEquivalentToUst { ust_count } => to_binary(&(ust_count * Uint128::from(2u128))),
+            MockPriceQueryMsg::QueryPlatformFees { msg: _ } => to_binary(&Uint128::from(1000u128)),
+            MockPriceQueryMsg::Consult { token: _, amount } => to_binary(&(amount * Uint128::from(3u128))),
+        }
+    }
+
+    /// An app with the gaming contract pricing bids through the mock proxy,
+    /// an open oneToOne pool and a gamer allowing it 1000000 FURY
+    fn proxy_app() -> (App, Addr, Addr, Addr) {
+        let mut app: App = AppBuilder::new().build();
+        let admin = Addr::unchecked("admin11111");
+        let gamer = Addr::unchecked("gamer001");
+        app.init_bank_balance(&gamer, vec![coin(10000000, "uusd")]).unwrap();
+
+        let proxy_code = app.store_code(Box::new(ContractWrapper::new(
+            mock_proxy_execute,
+            mock_proxy_instantiate,
+            mock_proxy_query,
+        )));
+        let token_code = app.store_code(Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        )));
+        let gaming_code = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply)));
+
+        let proxy = app
+            .instantiate_contract(proxy_code, admin.clone(), &Empty {}, &[], "proxy", None)
+            .unwrap();
+        app.init_bank_balance(&proxy, vec![coin(1000000000, "uusd")]).unwrap();
+        let token = app
+            .instantiate_contract(
+                token_code,
+                admin.clone(),
+                &Cw20InstantiateMsg {
+                    name: "Fury".to_string(),
+                    symbol: "FURY".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![Cw20Coin {
+                        address: gamer.to_string(),
+                        amount: Uint128::from(1000000u128),
+                    }],
+                    mint: None,
+                    marketing: None,
+                },
+                &[],
+                "fury",
+                None,
+            )
+            .unwrap();
+        let gaming = app
+            .instantiate_contract(
+                gaming_code,
+                admin.clone(),
+                &InstantiateMsg {
+                    transaction_fee: Uint128::from(100u128),
+                    minting_contract_address: token.to_string(),
+                    admin_address: admin.to_string(),
+                    platform_fee: Uint128::from(300u128),
+                    platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+                    astro_proxy_address: proxy.to_string(),
+                    price_source: None,
+                },
+                &[],
+                "gaming",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            admin.clone(),
+            gaming.clone(),
+            &ExecuteMsg::CreateGame { game_id: "Game001".to_string(), lock_at: None, settle_deadline: None },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            admin.clone(),
+            gaming.clone(),
+            &ExecuteMsg::SetPoolTypeParams {
+                pool_type: "oneToOne".to_string(),
+                pool_fee: Uint128::from(144262u128),
+                min_teams_for_pool: 2,
+                max_teams_for_pool: 10,
+                max_teams_for_gamer: 2,
+                wallet_percentages: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            admin.clone(),
+            gaming.clone(),
+            &ExecuteMsg::CreatePool { game_id: "Game001".to_string(), pool_type: "oneToOne".to_string() },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            gamer.clone(),
+            token.clone(),
+            &Cw20ExecuteMsg::IncreaseAllowance {
+                spender: gaming.to_string(),
+                amount: Uint128::from(1000000u128),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        (app, gaming, token, proxy)
+    }
+
+    fn bid_msg(team_id: &str, amount: u128) -> ExecuteMsg {
+        limited_bid_msg(team_id, amount, None, None, None)
+    }
+
+    fn limited_bid_msg(
+        team_id: &str,
+        amount: u128,
+        max_spread: Option<Decimal>,
+        belief_price: Option<Decimal>,
+        min_receive: Option<u128>,
+    ) -> ExecuteMsg {
+        ExecuteMsg::GamePoolBidSubmitCommand {
+            gamer: "gamer001".to_string(),
+            pool_type: "oneToOne".to_string(),
+            pool_id: "1".to_string(),
+            team_id: team_id.to_string(),
+            amount: Uint128::from(amount),
+            max_spread,
+            belief_price,
+            min_receive: min_receive.map(Uint128::from),
+        }
+    }
+
+    #[test]
+    fn test_bid_is_priced_by_the_astro_proxy() {
+        let (mut app, gaming, token, proxy) = proxy_app();
+        let gamer = Addr::unchecked("gamer001");
+
+        // the proxy prices the 144262 uusd pool fee at 288524 FURY
+        let err = app
+            .execute_contract(gamer.clone(), gaming.clone(), &bid_msg("Team001", 144262), &[coin(10000, "uusd")])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Std(StdError::generic_err("Amount being bid does not match the pool fee and the platform fee"))
+        );
+
+        // 3% platform fee and 1% transaction fee of the pool fee, in uusd
+        let err = app
+            .execute_contract(gamer.clone(), gaming.clone(), &bid_msg("Team001", 288524), &[coin(5000, "uusd")])
+            .unwrap_err();
+        assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::InsufficientFeesUst {});
+        let err = app
+            .execute_contract(gamer.clone(), gaming.clone(), &bid_msg("Team001", 288524), &[])
+            .unwrap_err();
+        assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::InvalidNumberOfCoinsSent {});
+
+        app.execute_contract(gamer.clone(), gaming.clone(), &bid_msg("Team001", 288524), &[coin(10000, "uusd")])
+            .unwrap();
+        let teams: Vec<PoolTeamDetails> = app
+            .wrap()
+            .query_wasm_smart(gaming.clone(), &QueryMsg::PoolTeamDetails { pool_id: "1".to_string() })
+            .unwrap();
+        assert_eq!(teams.len(), 1);
+        assert_eq!(teams[0].gamer_address, "gamer001".to_string());
+        let balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(token, &Cw20QueryMsg::Balance { address: gaming.to_string() })
+            .unwrap();
+        assert_eq!(balance.balance, Uint128::from(288524u128));
+        // the swap fee quoted by the proxy goes along with the swap
+        assert_eq!(
+            app.wrap().query_balance(proxy, "uusd").unwrap().amount,
+            Uint128::from(1000000000u128 + 1000u128 - 144262u128)
+        );
+        assert_eq!(
+            app.wrap().query_balance(gaming, "uusd").unwrap().amount,
+            Uint128::from(10000u128 - 1000u128 + 144262u128)
+        );
+    }
+
+    #[test]
+    fn test_set_price_source() {
+        let (mut app, gaming, _token, proxy) = proxy_app();
+        let gamer = Addr::unchecked("gamer001");
+        let admin = Addr::unchecked("admin11111");
+
+        let err = app
+            .execute_contract(
+                gamer.clone(),
+                gaming.clone(),
+                &ExecuteMsg::SetPriceSource { price_source: PriceSource::Fixed { fury_per_ust: Decimal::one() } },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized { invoker: "gamer001".to_string() }
+        );
+        let err = app
+            .execute_contract(
+                admin.clone(),
+                gaming.clone(),
+                &ExecuteMsg::SetPriceSource { price_source: PriceSource::Fixed { fury_per_ust: Decimal::zero() } },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Std(StdError::generic_err("Fixed price must not be zero"))
+        );
+
+        // one and a half FURY per UST
+        app.execute_contract(
+            admin.clone(),
+            gaming.clone(),
+            &ExecuteMsg::SetPriceSource {
+                price_source: PriceSource::Fixed { fury_per_ust: Decimal::from_ratio(3u128, 2u128) },
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            admin.clone(),
+            proxy.clone(),
+            &MockProxyExecuteMsg::SetReturnRate { ust_per_fury: Decimal::from_ratio(2u128, 3u128) },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(gamer.clone(), gaming.clone(), &bid_msg("Team001", 144262), &[coin(10000, "uusd")])
+            .unwrap_err();
+        app.execute_contract(gamer.clone(), gaming.clone(), &bid_msg("Team001", 216393), &[coin(10000, "uusd")])
+            .unwrap();
+
+        // the mock proxy also answers as the oracle, at 3 FURY per UST
+        app.execute_contract(
+            admin.clone(),
+            gaming.clone(),
+            &ExecuteMsg::SetPriceSource { price_source: PriceSource::Twap { oracle_address: proxy.clone() } },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            admin.clone(),
+            proxy,
+            &MockProxyExecuteMsg::SetReturnRate { ust_per_fury: Decimal::from_ratio(1u128, 3u128) },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(gamer.clone(), gaming.clone(), &bid_msg("Team002", 288524), &[coin(10000, "uusd")])
+            .unwrap_err();
+        app.execute_contract(gamer.clone(), gaming.clone(), &bid_msg("Team002", 432786), &[coin(10000, "uusd")])
+            .unwrap();
+        let teams: Vec<PoolTeamDetails> = app
+            .wrap()
+            .query_wasm_smart(gaming, &QueryMsg::PoolTeamDetails { pool_id: "1".to_string() })
+            .unwrap();
+        assert_eq!(teams.len(), 2);
+    }
+
+    #[test]
+    fn test_bid_swap_slippage() {
+        let (mut app, gaming, token, proxy) = proxy_app();
+        let gamer = Addr::unchecked("gamer001");
+        let pool_teams = |app: &App| -> usize {
+            let teams: Vec<PoolTeamDetails> = app
+                .wrap()
+                .query_wasm_smart(gaming.clone(), &QueryMsg::AllTeams {})
+                .unwrap();
+            teams.len()
+        };
+
+        let err = app
+            .execute_contract(
+                gamer.clone(),
+                gaming.clone(),
+                &limited_bid_msg("Team001", 288524, Some(Decimal::percent(60)), None, None),
+                &[coin(10000, "uusd")],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Std(StdError::generic_err("Max spread cannot be over 50%"))
+        );
+
+        // the pool is moved 10% against the swap
+        app.execute_contract(
+            gamer.clone(),
+            proxy.clone(),
+            &MockProxyExecuteMsg::SetReturnRate { ust_per_fury: Decimal::percent(45) },
+            &[],
+        )
+        .unwrap();
+        let err = app
+            .execute_contract(gamer.clone(), gaming.clone(), &bid_msg("Team001", 288524), &[coin(10000, "uusd")])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Std(StdError::generic_err(
+                "Swap returned 129835 uusd, less than the minimum receive of 143541"
+            ))
+        );
+        // nothing of the bid is kept
+        assert_eq!(pool_teams(&app), 0);
+        let balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(token.clone(), &Cw20QueryMsg::Balance { address: gamer.to_string() })
+            .unwrap();
+        assert_eq!(balance.balance, Uint128::from(1000000u128));
+        assert_eq!(app.wrap().query_balance(gamer.clone(), "uusd").unwrap().amount, Uint128::from(10000000u128));
+
+        // a minimum receive above the pool fee less the spread is kept
+        let err = app
+            .execute_contract(
+                gamer.clone(),
+                gaming.clone(),
+                &limited_bid_msg("Team001", 288524, Some(Decimal::percent(20)), None, Some(140000)),
+                &[coin(10000, "uusd")],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Std(StdError::generic_err(
+                "Swap returned 129835 uusd, less than the minimum receive of 140000"
+            ))
+        );
+        // and the belief price goes to the pair
+        let err = app
+            .execute_contract(
+                gamer.clone(),
+                gaming.clone(),
+                &limited_bid_msg("Team001", 288524, Some(Decimal::percent(5)), Some(Decimal::percent(200)), None),
+                &[coin(10000, "uusd")],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<StdError>().unwrap(),
+            StdError::generic_err("Operation exceeds max spread limit")
+        );
+        assert_eq!(pool_teams(&app), 0);
+
+        let rsp = app
+            .execute_contract(
+                gamer.clone(),
+                gaming.clone(),
+                &limited_bid_msg("Team001", 288524, Some(Decimal::percent(20)), Some(Decimal::percent(200)), None),
+                &[coin(10000, "uusd")],
+            )
+            .unwrap();
+        // the reply takes the fee sent with the swap out of what it returned
+        let received = rsp
+            .events
+            .iter()
+            .flat_map(|event| event.attributes.iter())
+            .find(|attribute| attribute.key == "swap_received")
+            .map(|attribute| attribute.value.clone());
+        assert_eq!(received, Some("129835".to_string()));
+        assert_eq!(pool_teams(&app), 1);
+        assert_eq!(
+            app.wrap().query_balance(gaming, "uusd").unwrap().amount,
+            Uint128::from(10000u128 - 1000u128 + 129835u128)
+        );
+    }
+
+    #[test]
+    fn test_bid_is_rejected_while_a_swap_is_pending() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            transaction_fee: Uint128::from(100000u128),
+            minting_contract_address: "cwtoken11111".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            platform_fees_collector_wallet: "FEE_WALLET".to_string(),
+            astro_proxy_address: "ASTROPORT".to_string(),
+            price_source: Some(PriceSource::Fixed { fury_per_ust: Decimal::one() }),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(deps.as_mut(), mock_env(), adminInfo.clone(), instantiate_msg).unwrap();
+        create_game(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), None, None).unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            1,
+            2,
+            1,
+            vec![],
+        )
+        .unwrap();
+        create_pool(deps.as_mut(), mock_env(), adminInfo.clone(), "Game001".to_string(), "oneToOne".to_string()).unwrap();
+        PENDING_BID_SWAP
+            .save(
+                &mut deps.storage,
+                &PendingBidSwap {
+                    ust_balance_before: Uint128::from(1000u128),
+                    min_receive: Uint128::from(143541u128),
+                },
+            )
+            .unwrap();
+
+        let err = game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("Gamer001", &[coin(10000000, "uusd")]),
+            "Gamer001".to_string(),
+            "oneToOne".to_string(),
+            "1".to_string(),
+            "Team001".to_string(),
+            Uint128::from(144262u128),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Std(StdError::generic_err("Another bid swap is still pending")));
+        assert_eq!(query_pool_details(&deps.storage, "1".to_string()).unwrap().current_teams_count, 0);
+    }
+}
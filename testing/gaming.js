@@ -27,6 +27,7 @@ let gaming_contract_address = ""
 let proxy_contract_address = "terra17z8hs6xyfdpsyf564xkjwfwp423zkkam6hquh5"
 let fury_contract_address = "terra1zjthyw8e8jayngkvg5kddccwa9v46s4w9sq2pq"
 const gamer = treasury_wallet.key.accAddress
+const game_id = "Game001"
 // const gamer_extra_1 = walletTest3.key.accAddress
 // const gamer_extra_2 = walletTest4.key.accAddress
 
@@ -36,7 +37,6 @@ const gaming_init = {
     "admin_address": walletTest1.key.accAddress,
     "platform_fee": "1",
     "transaction_fee": "1",
-    "game_id": "Game001",
     "platform_fees_collector_wallet": walletTest1.key.accAddress,
     "astro_proxy_address": proxy_contract_address,
 }
@@ -74,11 +74,19 @@ let test_create_and_query_game = async function (time) {
     gaming_contract_address = await deploy_contract(GamingContractPath, gaming_init)
     console.log(`Gaming Address:${gaming_contract_address}`)
     await sleep(sleep_time)
+    console.log("Create Game")
+    let response = await executeContract(walletTest1, gaming_contract_address, {
+        create_game: {
+            "game_id": game_id
+        }
+    })
+    console.log(`Game Create TX : ${response.txhash}`)
+    await sleep(sleep_time)
     console.log("Executing Query For Contract Details")
     let query_resposne = await queryContract(gaming_contract_address, {
-        game_details: {}
+        game_details: {"game_id": game_id}
     })
-    assert.isTrue(gaming_init['game_id'] === query_resposne['game_id'])
+    assert.isTrue(game_id === query_resposne['game_id'])
     assert.isTrue(1 === query_resposne['game_status'])
     console.log("Assert Success")
     await sleep(time)
@@ -89,6 +97,7 @@ let test_create_and_query_pool = async function (time) {
     console.log("Create Pool")
     let response = await executeContract(walletTest1, gaming_contract_address, {
         create_pool: {
+            "game_id": game_id,
             "pool_type": "H2H"
         }
     })
@@ -210,7 +219,7 @@ const test_game_lock_once_pool_is_closed = async function (time) {
     console.log("Testing game lock once pool is filled/closed.")
 
     let response = await executeContract(walletTest1, gaming_contract_address, {
-        lock_game: {}
+        lock_game: {"game_id": game_id}
     })
     console.log(response)
     console.log("Assert Success")
@@ -221,7 +230,7 @@ const test_game_lock_once_pool_is_canceled = async function (time) {
     console.log("Testing game lock once pool is Cancelled.")
 
     let response = await executeContract(walletTest1, gaming_contract_address, {
-        cancel_game: {}
+        cancel_game: {"game_id": game_id}
     })
     console.log(response)
     console.log("Assert Success")
@@ -231,7 +240,7 @@ const test_game_lock_once_pool_is_canceled = async function (time) {
 //     ExecuteMsg::ClaimRefund { gamer } => claim_refund(deps, info, gamer, env),
 const claim = async function (time) {
     let expected_reward = await queryContract(gaming_contract_address, {
-            query_reward: {"gamer": gamer}
+            query_reward: {"gamer": gamer, "game_id": game_id}
         }
     )
     console.log(`Expected Reward Amount  ${expected_reward}`)
@@ -247,13 +256,13 @@ const reward_distribution_for_locked_game_for_H2H = async function (time) {
     console.log("Reward Distribution for locked game")
     let response = await executeContract(walletTest1, gaming_contract_address, {
         "game_pool_reward_distribute": {
-            "game_id": "Gamer001",
+            "game_id": game_id,
             "pool_id": "1",
             "game_winners":
                 [
                     {
                         "gamer_address": gamer,
-                        "game_id": "Gamer001",
+                        "game_id": game_id,
                         "team_id": "1",
                         "reward_amount": "5000000", // This will be in ufury
                         "refund_amount": "0",
@@ -316,6 +325,7 @@ async function test_game_pool_reward_distribute(time) {
 
     let response = await executeContract(walletTest1, gaming_contract_address, {
         game_pool_reward_distribute: {
+            game_id: game_id,
             pool_id: "1",
             game_winners: game_winners
         }
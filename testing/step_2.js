@@ -6,7 +6,7 @@ let gaming_contract_address = "terra1nkj868ysnsfmpld3edjs73dzn0kpxceees5krf"
 console.log("Testing game lock once pool is filled/closed.")
 
 let response = await executeContract(walletTest1, gaming_contract_address, {
-    lock_game: {}
+    lock_game: {"game_id": "Game001"}
 })
 console.log(response)
 console.log("Assert Success")
\ No newline at end of file